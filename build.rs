@@ -0,0 +1,32 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// With the `embed-inputs` feature, generates a table of the answers and all day inputs below `src`,
+// which the library includes so that the binary does not need these files at runtime
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-changed=src");
+
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    let mut days = fs::read_dir(&src)
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with('d') && src.join(name).join("input").is_file())
+        .collect::<Vec<_>>();
+    days.sort();
+    let files = ["answers".to_string()]
+        .into_iter()
+        .filter(|file| src.join(file).is_file())
+        .chain(days.iter().map(|day| format!("{day}/input")));
+
+    let table = files
+        .map(|file| format!("    ({file:?}, include_str!({:?})),\n", src.join(&file)))
+        .collect::<String>();
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(out, format!("&[\n{table}]\n")).unwrap();
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_2023::error::{Error, Result};
+use aoc_2023::{utils, SOLUTIONS};
+
+use crate::cli::BenchOptions;
+use crate::runner;
+
+struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    p95: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let mean = samples.iter().sum::<Duration>() / samples.len() as u32;
+        Stats {
+            min: samples[0],
+            median: percentile(&samples, 50.0),
+            mean,
+            p95: percentile(&samples, 95.0),
+        }
+    }
+}
+
+// Nearest-rank percentile of already sorted samples
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.max(1) - 1]
+}
+
+/// Runs every registered day repeatedly and prints timing statistics for parsing and each part.
+/// Medians are compared against the baseline of a previous run, if there is one.
+/// Returns whether the baseline could be saved, if requested.
+pub fn bench(options: &BenchOptions) -> bool {
+    let baseline_file = options.baseline.clone().unwrap_or_else(utils::default_baseline_path);
+    let baseline = load_baseline(&baseline_file);
+    let mut results: Vec<(String, Duration)> = Vec::new();
+    let mut regressions = 0;
+
+    println!(
+        "{:<5} {:<6} {:>10} {:>10} {:>10} {:>10} {:>10}  Change",
+        "Day", "Phase", "Min", "Median", "Mean", "P95", "Baseline"
+    );
+    for &solution in SOLUTIONS {
+        if let Some(reason) = runner::missing_input(solution) {
+            println!("{:<5} SKIPPED: {}", solution.name(), reason);
+            continue;
+        }
+        // A failing day would only produce meaningless timings, so it is reported and skipped
+        let input = match runner::read_input(solution, &None) {
+            Ok(input) => input,
+            Err(error) => {
+                println!("{:<5} FAILED: {}", solution.name(), error);
+                continue;
+            }
+        };
+        if let Some(error) = solution.run(&input).parts.into_iter().find_map(|p| p.answer.err()) {
+            println!("{:<5} FAILED: {}", solution.name(), error);
+            continue;
+        }
+        for _ in 0..options.warmup {
+            solution.run(&input);
+        }
+
+        // Samples for the parse phase and each part, in this order
+        let mut samples: Vec<(String, Vec<Duration>)> = Vec::new();
+        for _ in 0..options.runs {
+            let report = solution.run(&input);
+            let phases = [(String::from("parse"), report.parse_time)]
+                .into_iter()
+                .chain(report.parts.iter().map(|p| (p.part.to_string(), p.solve_time)));
+            for (i, (phase, time)) in phases.enumerate() {
+                match samples.get_mut(i) {
+                    Some((_, times)) => times.push(time),
+                    None => samples.push((phase, vec![time])),
+                }
+            }
+        }
+
+        for (phase, times) in samples {
+            let key = format!("{}:{}", solution.name(), phase);
+            let stats = Stats::from_samples(times);
+            let (baseline_col, change_col) = match baseline.get(&key) {
+                Some(&before) => {
+                    let change = (stats.median.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+                    let regression = change > options.threshold;
+                    regressions += regression as usize;
+                    let flag = if regression { "  REGRESSION" } else { "" };
+                    (format!("{:.2?}", before), format!("{:+.1}%{}", change, flag))
+                }
+                None => (String::from("-"), String::from("-")),
+            };
+            println!(
+                "{:<5} {:<6} {:>10} {:>10} {:>10} {:>10} {:>10}  {}",
+                solution.name(),
+                phase,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.p95),
+                baseline_col,
+                change_col
+            );
+            results.push((key, stats.median));
+        }
+    }
+
+    if !baseline.is_empty() {
+        println!("\n{} regression(s) beyond {}%", regressions, options.threshold);
+    }
+    if options.save {
+        if let Err(error) = save_baseline(&baseline_file, &results) {
+            eprintln!("{error}");
+            return false;
+        }
+        println!("\nSaved baseline to {}", baseline_file.display());
+    }
+    true
+}
+
+// The baseline holds the median of every day and phase in nanoseconds, e.g. "d01:parse 123456"
+fn load_baseline(file: &Path) -> HashMap<String, Duration> {
+    fs::read_to_string(file).map_or(HashMap::new(), |content| parse_baseline(&content))
+}
+
+fn parse_baseline(content: &str) -> HashMap<String, Duration> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .filter_map(|(key, nanos)| {
+            nanos
+                .trim()
+                .parse::<u64>()
+                .ok()
+                .map(|n| (key.to_string(), Duration::from_nanos(n)))
+        })
+        .collect()
+}
+
+fn save_baseline(file: &Path, results: &[(String, Duration)]) -> Result<()> {
+    let content = results
+        .iter()
+        .map(|(key, median)| format!("{} {}\n", key, median.as_nanos()))
+        .collect::<String>();
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|err| Error::new(format!("Cannot create {}: {err}", dir.display())))?;
+    }
+    fs::write(file, content).map_err(|err| Error::new(format!("Cannot write {}: {err}", file.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_baseline, Stats};
+
+    #[test]
+    fn should_compute_stats() {
+        let samples = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(10), stats.median);
+        assert_eq!(Duration::from_micros(10_500), stats.mean);
+        assert_eq!(Duration::from_millis(19), stats.p95);
+    }
+
+    #[test]
+    fn should_compute_stats_for_single_sample() {
+        let stats = Stats::from_samples(vec![Duration::from_millis(3)]);
+        assert_eq!(Duration::from_millis(3), stats.median);
+        assert_eq!(Duration::from_millis(3), stats.p95);
+    }
+
+    #[test]
+    fn should_parse_baseline() {
+        let baseline = parse_baseline("d01:parse 1500\nd01:1 2000000\n");
+        assert_eq!(Some(&Duration::from_nanos(1500)), baseline.get("d01:parse"));
+        assert_eq!(Some(&Duration::from_millis(2)), baseline.get("d01:1"));
+    }
+}
@@ -0,0 +1,280 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::report::Format;
+use crate::selector::{single_day, Selector};
+
+/// Where to read the puzzle input from, instead of the day's own `input` file
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Runs the days and parts chosen by any of the selectors, e.g. `all`, `d01` or `d03..d07:2`
+    Run(Vec<Selector>),
+    /// Compares the results of all days to the expected answers
+    Verify,
+    /// Runs all days repeatedly and reports timing statistics
+    Bench(BenchOptions),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    /// Number of measured runs per day
+    pub runs: usize,
+    /// Number of unmeasured runs per day before the measured ones
+    pub warmup: usize,
+    /// Slowdown of the median compared to the baseline, in percent, that is flagged as regression
+    pub threshold: f64,
+    /// Whether to store the results as baseline for later runs
+    pub save: bool,
+    /// File to load the baseline from and save it to, instead of the default one
+    pub baseline: Option<PathBuf>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            runs: 10,
+            warmup: 2,
+            threshold: 10.0,
+            save: false,
+            baseline: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub input: Option<InputSource>,
+    /// Directory to read the inputs and answers from, instead of the default input root
+    pub input_dir: Option<PathBuf>,
+    pub format: Format,
+    /// Number of days to run concurrently
+    pub jobs: usize,
+    /// Whether to read the input line by line instead of all at once
+    pub stream: bool,
+}
+
+impl Args {
+    /// Parses the command line arguments, without the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+        let mut targets = Vec::new();
+        let mut input = None;
+        let mut input_dir = None;
+        let mut format = Format::default();
+        let mut jobs = 1;
+        let mut stream = false;
+        let mut bench = BenchOptions::default();
+        let mut bench_option_used = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or(format!("Missing value for {name}"))
+            };
+            match name.as_str() {
+                "--input" => {
+                    let value = value()?;
+                    input = Some(if value == "-" { InputSource::Stdin } else { InputSource::File(value.into()) });
+                }
+                "--input-dir" => input_dir = Some(PathBuf::from(value()?)),
+                "--format" => format = value()?.parse()?,
+                "--jobs" => jobs = parse_value(&name, &value()?)?,
+                "--runs" => bench.runs = parse_value(&name, &value()?)?,
+                "--warmup" => bench.warmup = parse_value(&name, &value()?)?,
+                "--threshold" => bench.threshold = parse_value(&name, &value()?)?,
+                "--save" => bench.save = true,
+                "--baseline" => bench.baseline = Some(PathBuf::from(value()?)),
+                "--stream" => stream = true,
+                _ if name.starts_with("--") => return Err(format!("Unknown option: {name}")),
+                _ => targets.push(arg),
+            }
+            bench_option_used |= ["--runs", "--warmup", "--threshold", "--save", "--baseline"].contains(&name.as_str());
+        }
+
+        let command = match targets.first().ok_or("Provide the module as arg, e.g. d01")?.as_str() {
+            "verify" | "bench" if targets.len() > 1 => return Err(format!("Unexpected argument: {}", targets[1])),
+            "verify" => Command::Verify,
+            "bench" => Command::Bench(bench),
+            _ => Command::Run(
+                targets
+                    .iter()
+                    .map(|target| Selector::parse_list(target))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .concat(),
+            ),
+        };
+        let single_day = matches!(&command, Command::Run(selectors) if single_day(selectors).is_some());
+        if input.is_some() && !single_day {
+            return Err(String::from("--input can only be used with a single day"));
+        }
+        if bench_option_used && !matches!(command, Command::Bench(_)) {
+            return Err(String::from(
+                "--runs, --warmup, --threshold, --save and --baseline can only be used with bench",
+            ));
+        }
+        if format != Format::Text && !matches!(command, Command::Run(_)) {
+            return Err(String::from("--format can only be used when running days"));
+        }
+        if jobs != 1 && !matches!(command, Command::Run(_)) {
+            return Err(String::from("--jobs can only be used when running days"));
+        }
+        if stream && !matches!(command, Command::Run(_)) {
+            return Err(String::from("--stream can only be used when running days"));
+        }
+        if jobs == 0 {
+            return Err(String::from("--jobs has to be at least 1"));
+        }
+        if let Command::Bench(BenchOptions { runs: 0, .. }) = command {
+            return Err(String::from("--runs has to be at least 1"));
+        }
+        Ok(Args {
+            command,
+            input,
+            input_dir,
+            format,
+            jobs,
+            stream,
+        })
+    }
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .map_err(|_| format!("Invalid value for {name}: {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Args, BenchOptions, Command, InputSource};
+    use crate::report::Format;
+    use crate::selector::Selector;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn should_parse_day_without_input() {
+        let args = parse(&["d01"]).unwrap();
+        assert_eq!(Command::Run(vec!["d01".parse().unwrap()]), args.command);
+        assert_eq!(None, args.input);
+    }
+
+    #[test]
+    fn should_parse_several_selectors() {
+        let expected = Command::Run(Selector::parse_list("d01,d14,d03..d07:2").unwrap());
+        assert_eq!(expected, parse(&["d01,d14", "d03..d07:2"]).unwrap().command);
+    }
+
+    #[test]
+    fn should_parse_verify() {
+        assert_eq!(Command::Verify, parse(&["verify"]).unwrap().command);
+    }
+
+    #[test]
+    fn should_parse_input_file() {
+        let expected = Some(InputSource::File(PathBuf::from("other.txt")));
+        assert_eq!(expected, parse(&["d12", "--input", "other.txt"]).unwrap().input);
+        assert_eq!(expected, parse(&["--input=other.txt", "d12"]).unwrap().input);
+    }
+
+    #[test]
+    fn should_parse_input_dir() {
+        let args = parse(&["verify", "--input-dir", "/tmp/inputs"]).unwrap();
+        assert_eq!(Some(PathBuf::from("/tmp/inputs")), args.input_dir);
+        assert_eq!(None, parse(&["d01"]).unwrap().input_dir);
+    }
+
+    #[test]
+    fn should_parse_stream() {
+        assert!(parse(&["d01", "--input", "-", "--stream"]).unwrap().stream);
+        assert!(!parse(&["d01"]).unwrap().stream);
+        assert!(parse(&["verify", "--stream"]).is_err());
+    }
+
+    #[test]
+    fn should_parse_stdin() {
+        assert_eq!(Some(InputSource::Stdin), parse(&["d12", "--input", "-"]).unwrap().input);
+        assert_eq!(
+            Some(InputSource::Stdin),
+            parse(&["d12:2", "--input", "-"]).unwrap().input
+        );
+    }
+
+    #[test]
+    fn should_parse_bench_options() {
+        assert_eq!(
+            Command::Bench(BenchOptions::default()),
+            parse(&["bench"]).unwrap().command
+        );
+        let expected = BenchOptions {
+            runs: 50,
+            warmup: 5,
+            threshold: 2.5,
+            save: true,
+            baseline: Some(PathBuf::from("/tmp/baseline")),
+        };
+        let args = parse(&[
+            "bench",
+            "--runs",
+            "50",
+            "--warmup=5",
+            "--threshold",
+            "2.5",
+            "--save",
+            "--baseline",
+            "/tmp/baseline",
+        ])
+        .unwrap();
+        assert_eq!(Command::Bench(expected), args.command);
+    }
+
+    #[test]
+    fn should_parse_format() {
+        assert_eq!(Format::Text, parse(&["all"]).unwrap().format);
+        assert_eq!(Format::Json, parse(&["all", "--format", "json"]).unwrap().format);
+        assert_eq!(Format::Csv, parse(&["d01", "--format=csv"]).unwrap().format);
+    }
+
+    #[test]
+    fn should_parse_jobs() {
+        assert_eq!(1, parse(&["all"]).unwrap().jobs);
+        assert_eq!(4, parse(&["all", "--jobs", "4"]).unwrap().jobs);
+    }
+
+    #[test]
+    fn should_reject_invalid_args() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["d12", "--input"]).is_err());
+        assert!(parse(&["d12", "--unknown"]).is_err());
+        assert!(parse(&["d12", "d13x"]).is_err());
+        assert!(parse(&["verify", "d13"]).is_err());
+        assert!(parse(&["all", "--input", "other.txt"]).is_err());
+        assert!(parse(&["d12,d13", "--input", "other.txt"]).is_err());
+        assert!(parse(&["verify", "--input", "other.txt"]).is_err());
+        assert!(parse(&["d12", "--runs", "5"]).is_err());
+        assert!(parse(&["verify", "--baseline", "/tmp/baseline"]).is_err());
+        assert!(parse(&["bench", "--runs", "many"]).is_err());
+        assert!(parse(&["bench", "--runs", "0"]).is_err());
+        assert!(parse(&["all", "--format", "xml"]).is_err());
+        assert!(parse(&["verify", "--format", "json"]).is_err());
+        assert!(parse(&["all", "--jobs", "0"]).is_err());
+        assert!(parse(&["bench", "--jobs", "4"]).is_err());
+    }
+}
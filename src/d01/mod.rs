@@ -4,49 +4,80 @@ use std::cmp;
 use either::Left;
 use either::Right;
 
-use crate::utils;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::parse_streamed;
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day01;
 
-fn part_one() {
-    let start = utils::part_start(1);
-    println!("Result: {}", calc_line_sum("d01/input"));
-    utils::part_end(start);
-}
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    type Input = Vec<String>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> Result<i32> {
+        calc_line_sum(input)
+    }
 
-fn part_two() {
-    let start = utils::part_start(1);
-    println!("Result: {}", calc_line_sum_with_written_digits("d01/input"));
-    utils::part_end(start);
+    fn part_two(&self, input: &Vec<String>) -> Result<i32> {
+        calc_line_sum_with_written_digits(input)
+    }
+
+    fn solve_lines(&self, lines: &mut dyn Iterator<Item = Result<String>>) -> Option<Result<(i32, i32)>> {
+        let sums = parse_streamed(lines, parse_line)
+            .enumerate()
+            .try_fold((0, 0), |(one, two), (i, line)| {
+                let line = line?;
+                Ok((
+                    one + calibration_value(i, &line, false)?,
+                    two + calibration_value(i, &line, true)?,
+                ))
+            });
+        Some(sums)
+    }
 }
 
-fn calc_line_sum(file_path: &str) -> i32 {
-    utils::get_lines(file_path)
+pub fn calc_line_sum(lines: &[String]) -> Result<i32> {
+    lines
         .iter()
-        .map(|line| line.chars().filter(|c| c.is_digit(10)).collect::<String>())
-        .map(|num| format!("{}{}", num.chars().next().unwrap(), num.chars().last().unwrap()))
-        .map(|num| num.parse::<i32>().unwrap())
-        .sum::<i32>()
+        .enumerate()
+        .map(|(i, line)| calibration_value(i, line, false))
+        .sum::<Result<i32>>()
 }
 
-fn calc_line_sum_with_written_digits(file_path: &str) -> i32 {
-    utils::get_lines(file_path)
+pub fn calc_line_sum_with_written_digits(lines: &[String]) -> Result<i32> {
+    lines
         .iter()
-        .map(|line| {
-            format!(
-                "{}{}",
-                find_maybe_written_digit(line, false),
-                find_maybe_written_digit(line, true)
-            )
-        })
-        .map(|num| num.parse::<i32>().unwrap())
-        .sum::<i32>()
+        .enumerate()
+        .map(|(i, line)| calibration_value(i, line, true))
+        .sum::<Result<i32>>()
+}
+
+// Combines the first and last digit of the line with index `i`, optionally considering written digits too
+fn calibration_value(i: usize, line: &str, with_written_digits: bool) -> Result<i32> {
+    let digits = if with_written_digits {
+        (
+            find_maybe_written_digit(line, false),
+            find_maybe_written_digit(line, true),
+        )
+    } else {
+        let digit = |c: char| c.to_digit(10).map(|d| d as i32);
+        (line.chars().find_map(digit), line.chars().rev().find_map(digit))
+    };
+    match digits {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(Error::at_position(i + 1, 1, "No digit found")),
+    }
 }
 
-fn find_maybe_written_digit(target: &str, reversed: bool) -> i32 {
+fn find_maybe_written_digit(target: &str, reversed: bool) -> Option<i32> {
     let written = vec![
         ("one", '1'),
         ("two", '2'),
@@ -65,7 +96,7 @@ fn find_maybe_written_digit(target: &str, reversed: bool) -> i32 {
     range
         .find_map(|i| {
             let c = target.chars().nth(i).unwrap();
-            if c.is_digit(10) {
+            if c.is_ascii_digit() {
                 return Some(c);
             }
             written
@@ -79,47 +110,74 @@ fn find_maybe_written_digit(target: &str, reversed: bool) -> i32 {
                         }
                     },
                 )
-                .find_map(|c| c.map(|c| *c))
+                .find_map(|c| c.copied())
         })
         .map(|c| c.to_string().parse::<i32>().unwrap())
-        .unwrap()
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<String>> {
+    input.lines().map(|line| parse_line(input, line)).collect()
+}
+
+fn parse_line(input: &str, line: &str) -> Result<String> {
+    match line.find(|c: char| !c.is_ascii()) {
+        // Written digits are searched by byte index
+        Some(i) => Err(Error::at(input, &line[i..], "Unexpected non ASCII character")),
+        None => Ok(String::from(line)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn part_one_example() {
-        let res = super::calc_line_sum("d01/example_part_1");
-        assert_eq!(142, res);
+        let res = super::calc_line_sum(&super::parse_input(include_str!("example_part_1")).unwrap());
+        assert_eq!(142, res.unwrap());
     }
 
     #[test]
     fn part_two_example() {
-        let res = super::calc_line_sum_with_written_digits("d01/example_part_2");
-        assert_eq!(281, res);
+        let res =
+            super::calc_line_sum_with_written_digits(&super::parse_input(include_str!("example_part_2")).unwrap());
+        assert_eq!(281, res.unwrap());
+    }
+
+    #[test]
+    fn should_solve_streamed_lines() {
+        use crate::solution::Solution;
+
+        let mut lines = include_str!("example_part_1").lines().map(|line| Ok(line.to_string()));
+        let res = super::Day01.solve_lines(&mut lines).unwrap();
+        assert_eq!((142, 142), res.unwrap());
     }
 
     #[test]
     fn find_char_digit() {
         let res = super::find_maybe_written_digit("de4jco3kd", false);
-        assert_eq!(4, res);
+        assert_eq!(Some(4), res);
     }
 
     #[test]
     fn find_char_digit_from_end() {
         let res = super::find_maybe_written_digit("de4jco3kd", true);
-        assert_eq!(3, res);
+        assert_eq!(Some(3), res);
     }
 
     #[test]
     fn find_written_digit() {
         let res = super::find_maybe_written_digit("dtwonee4jco3kd", false);
-        assert_eq!(2, res);
+        assert_eq!(Some(2), res);
     }
 
     #[test]
     fn find_written_digit_from_end() {
         let res = super::find_maybe_written_digit("de4jco3ktwoned", true);
-        assert_eq!(1, res);
+        assert_eq!(Some(1), res);
+    }
+
+    #[test]
+    fn should_locate_line_without_digit() {
+        let error = super::calc_line_sum(&super::parse_input("a1b\nabc").unwrap()).unwrap_err();
+        assert_eq!("line 2, column 1: No digit found", error.to_string());
     }
 }
@@ -1,101 +1,125 @@
 use std::cmp::max;
-use std::vec::IntoIter;
 
-use crate::utils;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{each_line, numbered, parse_at, parse_streamed, separated, split_once_at};
 
-pub fn main() {
-    part_one();
-    part_two();
+// Game number and all of its draws as (count, color)
+pub type Game = (i32, Vec<(i32, String)>);
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+
+    type Input = Vec<Game>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<Game>> {
+        parse_lines(input)
+    }
+
+    fn part_one(&self, input: &Vec<Game>) -> Result<i32> {
+        Ok(calc_valid_game_sum(input))
+    }
+
+    fn part_two(&self, input: &Vec<Game>) -> Result<i32> {
+        Ok(calc_min_power_sum(input))
+    }
+
+    fn solve_lines(&self, lines: &mut dyn Iterator<Item = Result<String>>) -> Option<Result<(i32, i32)>> {
+        let sums = parse_streamed(lines, parse_line).try_fold((0, 0), |(one, two), game| {
+            let game = game?;
+            Ok((
+                one + if is_possible(&game) { game.0 } else { 0 },
+                two + min_power(&game),
+            ))
+        });
+        Some(sums)
+    }
 }
 
-fn part_one() {
-    let start = utils::part_start(1);
-    println!("Result: {}", calc_valid_game_sum("d02/input"));
-    utils::part_end(start);
+pub fn calc_valid_game_sum(games: &[Game]) -> i32 {
+    games.iter().filter(|game| is_possible(game)).map(|(i, _)| i).sum()
 }
 
-fn part_two() {
-    let start = utils::part_start(2);
-    println!("Result: {}", calc_min_power_sum("d02/input"));
-    utils::part_end(start);
+pub fn calc_min_power_sum(games: &[Game]) -> i32 {
+    games.iter().map(min_power).sum()
 }
 
-fn calc_valid_game_sum(file_path: &str) -> i32 {
+fn is_possible((_, draws): &Game) -> bool {
     // red, green, blue
     let max = (12, 13, 14);
 
-    parse_lines(file_path)
-        .filter(|(_, draws)| {
-            draws
-                .iter()
-                .map(|draw| draw.split_once(" ").unwrap())
-                .all(|(num_str, col)| {
-                    let num = num_str.parse::<i32>().unwrap();
-                    match col {
-                        "red" => num <= max.0,
-                        "green" => num <= max.1,
-                        "blue" => num <= max.2,
-                        _ => unreachable!("Invalid color: {}", col),
-                    }
-                })
-        })
-        .map(|(i, _)| i)
-        .sum()
+    draws.iter().all(|(num, col)| match col.as_str() {
+        "red" => *num <= max.0,
+        "green" => *num <= max.1,
+        "blue" => *num <= max.2,
+        _ => unreachable!("Invalid color: {}", col),
+    })
 }
 
-fn calc_min_power_sum(file_path: &str) -> i32 {
-    parse_lines(file_path)
-        .map(|(_, draws)| {
-            draws.iter().map(|draw| draw.split_once(" ").unwrap()).fold(
-                (0, 0, 0),
-                |acc, (num_str, col)| {
-                    let num = num_str.parse::<i32>().unwrap();
-                    match col {
-                        "red" => (max(num, acc.0), acc.1, acc.2),
-                        "green" => (acc.0, max(num, acc.1), acc.2),
-                        "blue" => (acc.0, acc.1, max(num, acc.2)),
-                        _ => unreachable!("Invalid color: {}", col),
-                    }
-                },
-            )
-        })
-        .map(|(a, b, c)| a * b * c)
-        .sum()
+fn min_power((_, draws): &Game) -> i32 {
+    let (red, green, blue) = draws.iter().fold((0, 0, 0), |acc, (num, col)| match col.as_str() {
+        "red" => (max(*num, acc.0), acc.1, acc.2),
+        "green" => (acc.0, max(*num, acc.1), acc.2),
+        "blue" => (acc.0, acc.1, max(*num, acc.2)),
+        _ => unreachable!("Invalid color: {}", col),
+    });
+    red * green * blue
 }
 
-fn parse_lines(file_path: &str) -> IntoIter<(i32, Vec<String>)> {
-    let res = utils::get_lines(file_path)
-        .iter()
-        .map(|line| line.split_once(":").unwrap())
-        .map(|(game, draws)| {
-            (
-                game.chars()
-                    .filter(|c| c.is_digit(10))
-                    .collect::<String>()
-                    .parse::<i32>()
-                    .unwrap(),
-                draws
-                    .split(|c| c == ';' || c == ',')
-                    .map(|draw| draw.trim().to_string())
-                    .collect::<Vec<String>>(),
-            )
-        })
-        .collect::<Vec<(i32, Vec<String>)>>();
-
-    res.into_iter()
+pub fn parse_lines(input: &str) -> Result<Vec<Game>> {
+    each_line(input, |line| parse_line(input, line))
+}
+
+fn parse_line(input: &str, line: &str) -> Result<Game> {
+    let (game_number, draws) = numbered(input, line, "Game")?;
+    let draws = separated(draws, ";", |draw| {
+        separated(draw, ",", |cubes| parse_cubes(input, cubes))
+    })?;
+    Ok((game_number, draws.concat()))
+}
+
+// Count and color of cubes like "3 blue"
+fn parse_cubes(input: &str, cubes: &str) -> Result<(i32, String)> {
+    let (num, col) = split_once_at(input, cubes, " ")?;
+    if !["red", "green", "blue"].contains(&col) {
+        return Err(Error::at(input, col, format!("Invalid color {col:?}")));
+    }
+    Ok((parse_at(input, num)?, col.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn part_one_example() {
-        let res = super::calc_valid_game_sum("d02/example");
+        let res = super::calc_valid_game_sum(&super::parse_lines(include_str!("example")).unwrap());
         assert_eq!(8, res);
     }
 
     #[test]
     fn part_two_example() {
-        let res = super::calc_min_power_sum("d02/example");
+        let res = super::calc_min_power_sum(&super::parse_lines(include_str!("example")).unwrap());
         assert_eq!(2286, res);
     }
+
+    #[test]
+    fn should_locate_invalid_color() {
+        let error = super::parse_lines("Game 1: 3 blue\nGame 2: 4 red, 1 pink").unwrap_err();
+        assert_eq!("line 2, column 18: Invalid color \"pink\"", error.to_string());
+    }
+
+    #[test]
+    fn should_locate_invalid_color_in_streamed_lines() {
+        use crate::solution::Solution;
+
+        let mut lines = ["Game 1: 3 blue", "Game 2: 4 red, 1 pink"]
+            .map(|line| Ok(line.to_string()))
+            .into_iter();
+        let error = super::Day02.solve_lines(&mut lines).unwrap().unwrap_err();
+        assert_eq!("line 2, column 18: Invalid color \"pink\"", error.to_string());
+    }
 }
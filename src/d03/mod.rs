@@ -1,38 +1,44 @@
 use itertools::Itertools;
 
-use crate::utils;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day03;
 
-fn part_one() {
-    let start = utils::part_start(1);
-    println!("Result: {}", find_included_number_sum("d03/input"));
-    utils::part_end(start);
-}
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+
+    type Input = Vec<String>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> Result<i32> {
+        find_included_number_sum(input)
+    }
 
-fn part_two() {
-    let start = utils::part_start(2);
-    println!("Result: {}", find_gear_ratio_sum("d03/input"));
-    utils::part_end(start);
+    fn part_two(&self, input: &Vec<String>) -> Result<i32> {
+        find_gear_ratio_sum(input)
+    }
 }
 
-fn find_included_number_sum(file_path: &str) -> i32 {
-    let lines = utils::get_lines(file_path);
-    let nums = find_numbers_with_positions(&lines);
-    let included_pos = find_included_positions(&lines);
-    nums.iter()
+pub fn find_included_number_sum(lines: &[String]) -> Result<i32> {
+    let nums = find_numbers_with_positions(lines)?;
+    let included_pos = find_included_positions(lines);
+    Ok(nums
+        .iter()
         .filter(|(_, num_pos)| overlap(&included_pos, num_pos))
         .map(|(num, _)| *num)
-        .sum()
+        .sum())
 }
 
-fn find_gear_ratio_sum(file_path: &str) -> i32 {
-    let lines = utils::get_lines(file_path);
-    let nums = find_numbers_with_positions(&lines);
-    lines
+pub fn find_gear_ratio_sum(lines: &[String]) -> Result<i32> {
+    let nums = find_numbers_with_positions(lines)?;
+    Ok(lines
         .iter()
         .enumerate()
         .flat_map(|(ri, line)| {
@@ -50,38 +56,45 @@ fn find_gear_ratio_sum(file_path: &str) -> i32 {
         })
         .filter(|n| n.len() == 2)
         .map(|n| n.iter().product::<i32>())
-        .sum()
+        .sum())
 }
 
-fn find_numbers_with_positions(lines: &Vec<String>) -> Vec<(i32, Vec<(usize, usize)>)> {
-    let mut nums: Vec<(i32, Vec<(usize, usize)>)> = Vec::new();
+// Number together with the positions of all of its digits
+type NumberWithPositions = (i32, Vec<(usize, usize)>);
+
+fn find_numbers_with_positions(lines: &[String]) -> Result<Vec<NumberWithPositions>> {
+    let mut nums: Vec<NumberWithPositions> = Vec::new();
     let mut cur_num: Option<(String, Vec<(usize, usize)>)> = None;
     for (ri, line) in lines.iter().enumerate() {
         for (ci, c) in line.chars().enumerate() {
-            if c.is_digit(10) {
+            if c.is_ascii_digit() {
                 cur_num = cur_num.or_else(|| Some((String::new(), Vec::new())));
                 cur_num.as_mut().unwrap().0.push(c);
                 cur_num.as_mut().unwrap().1.push((ri, ci));
             }
-            if cur_num.is_some() && (ci == line.len() - 1 || !c.is_digit(10)) {
-                let next = cur_num.unwrap();
-                nums.push((next.0.parse::<i32>().unwrap(), next.1));
+            if cur_num.is_some() && (ci == line.len() - 1 || !c.is_ascii_digit()) {
+                let (digits, positions) = cur_num.unwrap();
+                let num = digits.parse::<i32>().map_err(|_| {
+                    let (ri, ci) = positions[0];
+                    Error::at_position(ri + 1, ci + 1, format!("{digits} does not fit into an i32"))
+                })?;
+                nums.push((num, positions));
                 cur_num = None;
             }
         }
     }
 
-    nums
+    Ok(nums)
 }
 
-fn find_included_positions(lines: &Vec<String>) -> Vec<(usize, usize)> {
+fn find_included_positions(lines: &[String]) -> Vec<(usize, usize)> {
     lines
         .iter()
         .enumerate()
         .flat_map(|(ri, line)| {
             line.chars()
                 .enumerate()
-                .filter(|(_, c)| *c != '.' && !c.is_digit(10))
+                .filter(|(_, c)| *c != '.' && !c.is_ascii_digit())
                 .map(|(ci, _)| (ri, ci))
                 .flat_map(|(ri, ci)| get_adjacent_positions(ri, ci, lines.len(), line.len()))
                 .collect_vec()
@@ -89,15 +102,10 @@ fn find_included_positions(lines: &Vec<String>) -> Vec<(usize, usize)> {
         .collect_vec()
 }
 
-fn get_adjacent_positions(
-    center_ri: usize,
-    center_ci: usize,
-    max_ri: usize,
-    max_ci: usize,
-) -> Vec<(usize, usize)> {
+fn get_adjacent_positions(center_ri: usize, center_ci: usize, max_ri: usize, max_ci: usize) -> Vec<(usize, usize)> {
     let ri = center_ri as i32;
     let ci = center_ci as i32;
-    vec![
+    [
         (ri - 1, ci - 1),
         (ri - 1, ci),
         (ri - 1, ci + 1),
@@ -113,24 +121,37 @@ fn get_adjacent_positions(
     .collect_vec()
 }
 
-fn overlap<T>(a: &Vec<T>, b: &Vec<T>) -> bool
+fn overlap<T>(a: &[T], b: &[T]) -> bool
 where
     T: PartialEq,
 {
-    return a.iter().any(|el| b.iter().contains(el));
+    a.iter().any(|el| b.iter().contains(el))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<String>> {
+    Ok(input.lines().map(String::from).collect())
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::find_included_number_sum("d03/example");
-        assert_eq!(4361, res);
+        let res = super::find_included_number_sum(&super::parse_input(include_str!("example")).unwrap());
+        assert_eq!(4361, res.unwrap());
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::find_gear_ratio_sum("d03/example");
-        assert_eq!(467835, res);
+        let res = super::find_gear_ratio_sum(&super::parse_input(include_str!("example")).unwrap());
+        assert_eq!(467835, res.unwrap());
+    }
+
+    #[test]
+    fn should_locate_too_large_number() {
+        let res = super::find_included_number_sum(&super::parse_input("1.*\n.99999999999").unwrap());
+        assert_eq!(
+            "line 2, column 2: 99999999999 does not fit into an i32",
+            res.unwrap_err().to_string()
+        );
     }
 }
@@ -1,32 +1,60 @@
 use std::cmp::min;
+use std::collections::VecDeque;
 
 use itertools::Itertools;
 use num::pow;
 
-use crate::utils;
-use crate::utils::split_whitespace;
+use crate::error::Result;
+use crate::solution::Solution;
+use crate::utils::{each_line, numbered, parse_streamed, split_once_at, split_whitespace};
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+// Winning numbers and the numbers we have
+pub type Card = (Vec<i32>, Vec<i32>);
 
-fn part_one() {
-    let start = utils::part_start(1);
-    println!("Result: {}", get_card_point_sum("d04/input"));
-    utils::part_end(start);
-}
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    type Input = Vec<Card>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<Card>> {
+        parse_input(input)
+    }
 
-fn part_two() {
-    let start = utils::part_start(2);
-    println!("Result: {}", get_won_card_count("d04/input"));
-    utils::part_end(start);
+    fn part_one(&self, input: &Vec<Card>) -> Result<i32> {
+        Ok(get_card_point_sum(input))
+    }
+
+    fn part_two(&self, input: &Vec<Card>) -> Result<i32> {
+        Ok(get_won_card_count(input))
+    }
+
+    fn solve_lines(&self, lines: &mut dyn Iterator<Item = Result<String>>) -> Option<Result<(i32, i32)>> {
+        // Copies won for the next cards, which are at most as many as a card has winning numbers
+        let mut won_copies: VecDeque<i32> = VecDeque::new();
+        let sums = parse_streamed(lines, parse_line).try_fold((0, 0), |(points, count), card| {
+            let (wins, value) = get_card_value(&card?);
+            let copies = 1 + won_copies.pop_front().unwrap_or(0);
+            for j in 0..wins as usize {
+                match won_copies.get_mut(j) {
+                    Some(won) => *won += copies,
+                    None => won_copies.push_back(copies),
+                }
+            }
+            Ok((points + value, count + copies))
+        });
+        Some(sums)
+    }
 }
 
-fn get_won_card_count(file_path: &str) -> i32 {
-    let cards = get_card_values(file_path);
-    let mut counts: Vec<i32> = vec![1; cards.len()];
-    for (i, (wins, _)) in cards.iter().enumerate() {
+pub fn get_won_card_count(cards: &[Card]) -> i32 {
+    let card_values = get_card_values(cards);
+    let mut counts: Vec<i32> = vec![1; card_values.len()];
+    for (i, (wins, _)) in card_values.iter().enumerate() {
         let win_from = i + 1;
         let win_to = min(i + *wins as usize, counts.len() - 1);
         for j in win_from..=win_to {
@@ -37,32 +65,53 @@ fn get_won_card_count(file_path: &str) -> i32 {
     counts.iter().sum()
 }
 
-fn get_card_point_sum(file_path: &str) -> i32 {
-    get_card_values(file_path).iter().map(|vals| vals.1).sum()
+pub fn get_card_point_sum(cards: &[Card]) -> i32 {
+    get_card_values(cards).iter().map(|vals| vals.1).sum()
 }
 
-fn get_card_values(file_path: &str) -> Vec<(i32, i32)> {
-    utils::get_lines(file_path)
-        .iter()
-        .map(|line| line.split_once(':').unwrap().1.trim())
-        .map(|line| line.split_once('|').unwrap())
-        .map(|(win, mine)| (split_whitespace::<i32>(win), split_whitespace::<i32>(mine)))
-        .map(|(win, mine)| win.iter().filter(|&n| mine.contains(n)).count())
-        .map(|n| (n as i32, if n <= 0 { 0 } else { pow(2, n - 1) }))
-        .collect_vec()
+fn get_card_values(cards: &[Card]) -> Vec<(i32, i32)> {
+    cards.iter().map(get_card_value).collect_vec()
+}
+
+// Number of winning numbers we have and the points they are worth
+fn get_card_value((win, mine): &Card) -> (i32, i32) {
+    let n = win.iter().filter(|&n| mine.contains(n)).count();
+    (n as i32, if n == 0 { 0 } else { pow(2, n - 1) })
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Card>> {
+    each_line(input, |line| parse_line(input, line))
+}
+
+fn parse_line(input: &str, line: &str) -> Result<Card> {
+    let (_, numbers) = numbered::<u32>(input, line, "Card")?;
+    let (win, mine) = split_once_at(input, numbers, "|")?;
+    Ok((
+        split_whitespace::<i32>(input, win)?,
+        split_whitespace::<i32>(input, mine)?,
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::get_card_point_sum("d04/example");
+        let res = super::get_card_point_sum(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(13, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::get_won_card_count("d04/example");
+        let res = super::get_won_card_count(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(30, res);
     }
+
+    #[test]
+    fn should_solve_streamed_lines() {
+        use crate::solution::Solution;
+
+        let mut lines = include_str!("example").lines().map(|line| Ok(line.to_string()));
+        let res = super::Day04.solve_lines(&mut lines).unwrap();
+        assert_eq!((13, 30), res.unwrap());
+    }
 }
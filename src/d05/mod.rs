@@ -2,30 +2,39 @@ use std::cmp::min;
 
 use itertools::Itertools;
 
-use crate::utils::{get_lines, part_end, part_start, split_whitespace};
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{blocks, each_line, labelled_numbers, split_whitespace};
 
 // (source start, source end inclusive, destination start, destination end inclusive)
-type Mappings = Vec<(i64, i64, i64, i64)>;
+pub type Mappings = Vec<(i64, i64, i64, i64)>;
+// Seeds and all mappings in order from seed to location
+pub type Almanac = (Vec<i64>, Vec<Mappings>);
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day05;
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", get_min_location_for_seeds("d05/input"));
-    part_end(start);
-}
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    type Input = Almanac;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Almanac> {
+        parse_input(input)
+    }
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", get_min_location_for_seed_ranges("d05/input"));
-    part_end(start);
+    fn part_one(&self, input: &Almanac) -> Result<i64> {
+        Ok(get_min_location_for_seeds(input))
+    }
+
+    fn part_two(&self, input: &Almanac) -> Result<i64> {
+        Ok(get_min_location_for_seed_ranges(input))
+    }
 }
 
-fn get_min_location_for_seeds(file_path: &str) -> i64 {
-    let (seeds, mappings) = parse_input(file_path);
+pub fn get_min_location_for_seeds((seeds, mappings): &Almanac) -> i64 {
     seeds
         .iter()
         .map(|&s| {
@@ -40,12 +49,8 @@ fn get_min_location_for_seeds(file_path: &str) -> i64 {
         .unwrap()
 }
 
-fn get_min_location_for_seed_ranges(file_path: &str) -> i64 {
-    let (seeds, mappings) = parse_input(file_path);
-    let ranges = seeds
-        .chunks(2)
-        .map(|c| (c[0], c[0] + c[1] - 1))
-        .collect_vec();
+pub fn get_min_location_for_seed_ranges((seeds, mappings): &Almanac) -> i64 {
+    let ranges = seeds.chunks(2).map(|c| (c[0], c[0] + c[1] - 1)).collect_vec();
 
     ranges
         .iter()
@@ -79,8 +84,8 @@ fn get_min_location_for_seed_ranges(file_path: &str) -> i64 {
         .unwrap()
 }
 
-fn parse_input(file_path: &str) -> (Vec<i64>, Vec<Mappings>) {
-    let map_order = vec![
+pub fn parse_input(input: &str) -> Result<Almanac> {
+    let map_order = [
         "seed-to-soil",
         "soil-to-fertilizer",
         "fertilizer-to-water",
@@ -90,42 +95,71 @@ fn parse_input(file_path: &str) -> (Vec<i64>, Vec<Mappings>) {
         "humidity-to-location",
     ];
 
-    let lines = get_lines(file_path);
-    let seeds = split_whitespace::<i64>(lines.first().unwrap().split_once(": ").unwrap().1);
-
-    let mut mapping_index = 0usize;
-    let mut mappings: Vec<Mappings> = lines.iter().skip(2).filter(|line| !line.is_empty()).fold(
-        (0..map_order.len()).map(|_| Vec::new()).collect_vec(),
-        |mut acc, line| {
-            if line.ends_with(" map:") {
-                mapping_index = map_order.iter().position(|&x| line.starts_with(x)).unwrap();
-            } else {
-                let n = split_whitespace::<i64>(line);
-                acc[mapping_index].push((n[1], n[1] + n[2] - 1, n[0], n[0] + n[2] - 1));
-            }
-            acc
-        },
-    );
+    let mut blocks = blocks(input);
+    let seeds_line = blocks.next().ok_or_else(|| Error::new("Empty input"))?;
+    let seeds = labelled_numbers::<i64>(input, seeds_line, "seeds")?;
+    // Part two reads the seeds as pairs of range start and length
+    if seeds.is_empty() || seeds.len() % 2 != 0 {
+        return Err(Error::at(
+            input,
+            seeds_line,
+            "Expected a non-empty, even number of seeds",
+        ));
+    }
+
+    let mut mappings: Vec<Mappings> = (0..map_order.len()).map(|_| Vec::new()).collect_vec();
+    for block in blocks {
+        let (header, ranges) = block.split_once('\n').unwrap_or((block, ""));
+        let mapping_index = header
+            .strip_suffix(" map:")
+            .and_then(|name| map_order.iter().position(|&x| x == name))
+            .ok_or_else(|| Error::at(input, header, "Unknown map"))?;
+        let ranges = each_line(ranges, |line| match split_whitespace::<i64>(input, line)?[..] {
+            [dest, source, len] => Ok((source, source + len - 1, dest, dest + len - 1)),
+            _ => Err(Error::at(input, line, "Expected three numbers")),
+        })?;
+        mappings[mapping_index].extend(ranges);
+    }
 
     // sort mappings by source start
-    mappings
-        .iter_mut()
-        .for_each(|m| m.sort_by(|&a, &b| a.0.cmp(&b.0)));
+    mappings.iter_mut().for_each(|m| m.sort_by_key(|&a| a.0));
 
-    (seeds, mappings)
+    Ok((seeds, mappings))
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::get_min_location_for_seeds("d05/example");
+        let res = super::get_min_location_for_seeds(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(35, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::get_min_location_for_seed_ranges("d05/example");
+        let res = super::get_min_location_for_seed_ranges(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(46, res);
     }
+
+    #[test]
+    fn should_reject_empty_or_odd_seeds() {
+        let error = super::parse_input("seeds: 79 14 55").unwrap_err();
+        assert_eq!(
+            "line 1, column 1: Expected a non-empty, even number of seeds",
+            error.to_string()
+        );
+        let error = super::parse_input("seeds:").unwrap_err();
+        assert_eq!(
+            "line 1, column 1: Expected a non-empty, even number of seeds",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn should_locate_invalid_mapping() {
+        let error = super::parse_input("seeds: 1 2\n\nseed-to-soil map:\n1 2\n").unwrap_err();
+        assert_eq!("line 4, column 1: Expected three numbers", error.to_string());
+        let error = super::parse_input("seeds: 1 2\n\nseed-to-moon map:\n1 2 3").unwrap_err();
+        assert_eq!("line 3, column 1: Unknown map", error.to_string());
+    }
 }
@@ -2,57 +2,50 @@ use std::iter::zip;
 
 use itertools::Itertools;
 
-use crate::utils::{get_lines, part_end, part_start, split_whitespace};
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::labelled_numbers;
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+
+    type Input = Vec<(i64, i64)>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Vec<(i64, i64)>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Vec<(i64, i64)>) -> Result<i64> {
+        Ok(calc_win_options_product(input))
+    }
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", calc_win_options_product("d06/input"));
-    part_end(start);
+    fn part_two(&self, input: &Vec<(i64, i64)>) -> Result<i64> {
+        calc_win_options_product_single_race(input)
+    }
 }
 
-fn part_two() {
-    let start = part_start(2);
-    println!(
-        "Result: {}",
-        calc_win_options_product_single_race("d06/input")
-    );
-    part_end(start);
+pub fn calc_win_options_product(races: &[(i64, i64)]) -> i64 {
+    races.iter().map(|&(t, d)| calc_win_options_count(t, d)).product()
 }
 
-fn calc_win_options_product(file_path: &str) -> i64 {
-    let parsed = get_lines(file_path)
-        .iter()
-        .take(2)
-        .map(|line| line.split_once(':').unwrap().1)
-        .map(|nums| split_whitespace::<i64>(nums))
-        .collect_vec();
-    let races = zip(&parsed[0], &parsed[1]).collect_vec();
-
-    races
-        .iter()
-        .map(|(&t, &d)| calc_win_options_count(t, d))
-        .product()
+pub fn calc_win_options_product_single_race(races: &[(i64, i64)]) -> Result<i64> {
+    // The kerning was bad, all numbers actually form a single time and distance
+    let time = concat_numbers(races.iter().map(|(t, _)| t), "time")?;
+    let distance = concat_numbers(races.iter().map(|(_, d)| d), "distance")?;
+
+    Ok(calc_win_options_count(time, distance))
 }
 
-fn calc_win_options_product_single_race(file_path: &str) -> i64 {
-    let parsed = get_lines(file_path)
-        .iter()
-        .take(2)
-        .map(|line| {
-            line.chars()
-                .filter(|c| c.is_digit(10))
-                .join("")
-                .parse::<i64>()
-                .unwrap()
-        })
-        .collect_vec();
-
-    calc_win_options_count(parsed[0], parsed[1])
+fn concat_numbers<'a>(mut numbers: impl Iterator<Item = &'a i64>, name: &str) -> Result<i64> {
+    let digits = numbers.join("");
+    digits
+        .parse()
+        .map_err(|_| Error::new(format!("The {name} {digits} does not fit into an i64")))
 }
 
 fn calc_win_options_count(time: i64, distance: i64) -> i64 {
@@ -78,17 +71,39 @@ fn calc_win_options_count(time: i64, distance: i64) -> i64 {
     upper - lower + 1
 }
 
+pub fn parse_input(input: &str) -> Result<Vec<(i64, i64)>> {
+    let mut lines = input.lines();
+    let mut next_numbers = |label| labelled_numbers::<i64>(input, lines.next().unwrap_or_default(), label);
+    let times = next_numbers("Time")?;
+    let distances = next_numbers("Distance")?;
+    if times.len() != distances.len() {
+        return Err(Error::new("Expected the same number of times and distances"));
+    }
+
+    Ok(zip(times, distances).collect_vec())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::calc_win_options_product("d06/example");
+        let res = super::calc_win_options_product(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(288, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::calc_win_options_product_single_race("d06/example");
-        assert_eq!(71503, res);
+        let res = super::calc_win_options_product_single_race(&super::parse_input(include_str!("example")).unwrap());
+        assert_eq!(71503, res.unwrap());
+    }
+
+    #[test]
+    fn should_fail_for_too_large_single_race() {
+        let races = super::parse_input("Time: 9999999999 9999999999\nDistance: 1 1").unwrap();
+        let error = super::calc_win_options_product_single_race(&races).unwrap_err();
+        assert_eq!(
+            "The time 99999999999999999999 does not fit into an i64",
+            error.to_string()
+        );
     }
 }
@@ -4,7 +4,9 @@ use itertools::Itertools;
 
 use HandType::{FiveOfKind, FourOfKind, FullHouse, HighCard, OnePair, ThreeOfKind, TwoPair};
 
-use crate::utils::{get_lines, part_end, part_start};
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{parse_at, split_once_at};
 
 #[derive(PartialEq, PartialOrd, Ord, Eq, Debug)]
 enum HandType {
@@ -17,7 +19,7 @@ enum HandType {
     FiveOfKind,
 }
 
-#[derive(Eq, Ord)]
+#[derive(Eq)]
 struct Hand {
     cards: String,
     bid: i32,
@@ -30,57 +32,80 @@ impl PartialEq for Hand {
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
         if self.hand_type == other.hand_type {
-            return Some(self.cards.cmp(&other.cards));
+            return self.cards.cmp(&other.cards);
         }
-        Some(self.hand_type.cmp(&other.hand_type))
+        self.hand_type.cmp(&other.hand_type)
     }
 }
 
-pub fn main() {
-    part_one();
-    part_two();
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", calc_rank_bid_product_sum("d07/input", false));
-    part_end(start);
-}
+pub struct Day07;
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", calc_rank_bid_product_sum("d07/input", true));
-    part_end(start);
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Camel Cards";
+
+    type Input = Vec<(String, i32)>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Vec<(String, i32)>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Vec<(String, i32)>) -> Result<i64> {
+        Ok(calc_rank_bid_product_sum(input, false))
+    }
+
+    fn part_two(&self, input: &Vec<(String, i32)>) -> Result<i64> {
+        Ok(calc_rank_bid_product_sum(input, true))
+    }
 }
 
-fn calc_rank_bid_product_sum(file_path: &str, jokers: bool) -> i64 {
-    parse_input(file_path, jokers)
-        .into_iter()
+pub fn calc_rank_bid_product_sum(hands: &[(String, i32)], jokers: bool) -> i64 {
+    hands
+        .iter()
+        .map(|(hand, bid)| to_hand(hand, *bid, jokers))
         .sorted()
         .enumerate()
         .map(|(i, h)| (i as i64 + 1) * h.bid as i64)
         .sum()
 }
 
-fn parse_input(file_path: &str, jokers: bool) -> Vec<Hand> {
-    get_lines(file_path)
-        .iter()
-        .map(|line| line.split_once(' ').unwrap())
-        .map(|(hand, bid)| Hand {
-            // These replacements allow us to rely on standard alphanumeric sorting
-            cards: hand
-                .replace('A', "E")
-                .replace('K', "D")
-                .replace('Q', "C")
-                .replace('J', if jokers { "1" } else { "B" })
-                .replace('T', "A"),
-            bid: bid.parse::<i32>().unwrap(),
-            hand_type: determine_hand_type(hand, jokers),
+fn to_hand(hand: &str, bid: i32, jokers: bool) -> Hand {
+    Hand {
+        // These replacements allow us to rely on standard alphanumeric sorting
+        cards: hand
+            .replace('A', "E")
+            .replace('K', "D")
+            .replace('Q', "C")
+            .replace('J', if jokers { "1" } else { "B" })
+            .replace('T', "A"),
+        bid,
+        hand_type: determine_hand_type(hand, jokers),
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<(String, i32)>> {
+    input
+        .lines()
+        .map(|line| split_once_at(input, line, " "))
+        .map(|split| {
+            let (hand, bid) = split?;
+            if hand.len() != 5 || !hand.chars().all(|c| "23456789TJQKA".contains(c)) {
+                return Err(Error::at(input, hand, format!("Invalid hand {hand:?}")));
+            }
+            Ok((hand.to_string(), parse_at(input, bid)?))
         })
-        .collect_vec()
+        .collect()
 }
 
 fn determine_hand_type(hand: &str, jokers: bool) -> HandType {
@@ -101,8 +126,20 @@ fn determine_hand_type(hand: &str, jokers: bool) -> HandType {
     match counts.len() {
         5 => HighCard,
         4 => OnePair,
-        3 => return if count_max == 3 { ThreeOfKind } else { TwoPair },
-        2 => return if count_max == 4 { FourOfKind } else { FullHouse },
+        3 => {
+            if count_max == 3 {
+                ThreeOfKind
+            } else {
+                TwoPair
+            }
+        }
+        2 => {
+            if count_max == 4 {
+                FourOfKind
+            } else {
+                FullHouse
+            }
+        }
         1 => FiveOfKind,
         _ => unreachable!("Impossible hand card count: {}", counts.len()),
     }
@@ -114,13 +151,13 @@ mod tests {
 
     #[test]
     fn example_part_one() {
-        let res = calc_rank_bid_product_sum("d07/example", false);
+        let res = calc_rank_bid_product_sum(&parse_input(include_str!("example")).unwrap(), false);
         assert_eq!(6440, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = calc_rank_bid_product_sum("d07/example", true);
+        let res = calc_rank_bid_product_sum(&parse_input(include_str!("example")).unwrap(), true);
         assert_eq!(5905, res);
     }
 
@@ -1,93 +1,134 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{lcm_all, split_once_at};
 
-use crate::utils::{get_lines, part_end, part_start};
+// Instructions (true meaning "right") and the left/right node for each node
+pub type Network = (Vec<bool>, HashMap<String, [String; 2]>);
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day08;
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", count_steps_to_zzz("d08/input"));
-    part_end(start);
-}
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Haunted Wasteland";
+
+    type Input = Network;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Network> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Network) -> Result<i64> {
+        count_steps_to_zzz(input)
+    }
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", count_steps_to_ending_with_z("d08/input"));
-    part_end(start);
+    fn part_two(&self, input: &Network) -> Result<i64> {
+        count_steps_to_ending_with_z(input)
+    }
 }
 
-fn count_steps_to_zzz(file_path: &str) -> i64 {
-    let (instr, maps) = parse_input(file_path);
-    count_steps_to_end_with(&instr, &maps, "AAA", "ZZZ")
+pub fn count_steps_to_zzz((instr, maps): &Network) -> Result<i64> {
+    if !maps.contains_key("AAA") {
+        return Err(Error::new("There is no node AAA"));
+    }
+    count_steps_to_end_with(instr, maps, "AAA", "ZZZ").ok_or_else(|| Error::new("ZZZ is not reachable from AAA"))
 }
 
-fn count_steps_to_ending_with_z(file_path: &str) -> i64 {
-    let (instr, maps) = parse_input(file_path);
-    maps.keys()
+pub fn count_steps_to_ending_with_z((instr, maps): &Network) -> Result<i64> {
+    let steps = maps
+        .keys()
         .filter(|k| k.ends_with('A'))
-        .map(|k| count_steps_to_end_with(&instr, &maps, &k, "Z"))
-        .fold(1, num_integer::lcm)
+        .map(|k| {
+            count_steps_to_end_with(instr, maps, k, "Z")
+                .ok_or_else(|| Error::new(format!("No node ending with Z is reachable from {k}")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(lcm_all(steps))
 }
 
+// Steps until reaching a node ending with `end_with`, `None` if there is none on the way
 fn count_steps_to_end_with(
-    instr: &Vec<bool>,
+    instr: &[bool],
     maps: &HashMap<String, [String; 2]>,
     start: &str,
     end_with: &str,
-) -> i64 {
+) -> Option<i64> {
+    // After visiting every node at every instruction, the way only repeats
+    let max_steps = (maps.len() * instr.len()) as i64;
     let instr_count = instr.len() as i64;
     let mut i = 0i64;
     let mut cur = start;
     while !cur.ends_with(end_with) {
+        if i >= max_steps {
+            return None;
+        }
         let take_right = *instr.get((i % instr_count) as usize).unwrap();
         cur = maps.get(cur).unwrap()[take_right as usize].as_str();
         i += 1;
     }
 
-    i
+    Some(i)
 }
 
-fn parse_input(file_path: &str) -> (Vec<bool>, HashMap<String, [String; 2]>) {
-    let lines = get_lines(file_path);
-    let instr = lines
-        .first()
-        .unwrap()
-        .chars()
-        .map(|c| c == 'R')
-        .collect_vec();
+pub fn parse_input(input: &str) -> Result<Network> {
+    let mut lines = input.lines();
+    let first_line = lines.next().ok_or_else(|| Error::new("Empty input"))?;
+    let instr = first_line
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(false),
+            'R' => Ok(true),
+            _ => Err(Error::at(input, &first_line[i..], format!("Invalid instruction {c:?}"))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if instr.is_empty() {
+        return Err(Error::at(input, first_line, "No instructions"));
+    }
 
     let maps = lines
-        .iter()
-        .skip(2)
-        .map(|l| {
-            (
-                l[0..3].to_string(),
-                [l[7..10].to_string(), l[12..15].to_string()],
-            )
+        .skip(1)
+        .map(|line| {
+            let (node, targets) = split_once_at(input, line, " = ")?;
+            let (left, right) = targets
+                .strip_prefix('(')
+                .and_then(|t| t.strip_suffix(')'))
+                .and_then(|t| t.split_once(", "))
+                .ok_or_else(|| Error::at(input, targets, "Expected \"(<left>, <right>)\""))?;
+            Ok((node.to_string(), [left.to_string(), right.to_string()]))
         })
-        .collect::<_>();
+        .collect::<Result<HashMap<_, _>>>()?;
+    if let Some(unknown) = maps.values().flatten().find(|&node| !maps.contains_key(node)) {
+        return Err(Error::new(format!("Unknown node {unknown}")));
+    }
 
-    (instr, maps)
+    Ok((instr, maps))
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn examples_part_one() {
-        let mut res = super::count_steps_to_zzz("d08/example1");
-        assert_eq!(2, res);
-        res = super::count_steps_to_zzz("d08/example2");
-        assert_eq!(6, res);
+        let mut res = super::count_steps_to_zzz(&super::parse_input(include_str!("example1")).unwrap());
+        assert_eq!(2, res.unwrap());
+        res = super::count_steps_to_zzz(&super::parse_input(include_str!("example2")).unwrap());
+        assert_eq!(6, res.unwrap());
     }
 
     #[test]
     fn examples_part_two() {
-        let res = super::count_steps_to_ending_with_z("d08/example3");
-        assert_eq!(6, res);
+        let res = super::count_steps_to_ending_with_z(&super::parse_input(include_str!("example3")).unwrap());
+        assert_eq!(6, res.unwrap());
+    }
+
+    #[test]
+    fn should_fail_for_unreachable_end() {
+        let network = super::parse_input("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        let error = super::count_steps_to_zzz(&network).unwrap_err();
+        assert_eq!("ZZZ is not reachable from AAA", error.to_string());
+        let error = super::count_steps_to_ending_with_z(&network).unwrap_err();
+        assert_eq!("No node ending with Z is reachable from AAA", error.to_string());
     }
 }
@@ -1,76 +1,87 @@
-use itertools::Itertools;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{extrapolate, parse_streamed, split_whitespace};
 
-use crate::utils::{get_lines, part_end, part_start, split_whitespace};
+pub struct Day09;
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Mirage Maintenance";
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", calc_value_sum("d09/input", true));
-    part_end(start);
-}
+    type Input = Vec<Vec<i64>>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<i64>>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Vec<Vec<i64>>) -> Result<i64> {
+        Ok(calc_value_sum(input, true))
+    }
+
+    fn part_two(&self, input: &Vec<Vec<i64>>) -> Result<i64> {
+        Ok(calc_value_sum(input, false))
+    }
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", calc_value_sum("d09/input", false));
-    part_end(start);
+    fn solve_lines(&self, lines: &mut dyn Iterator<Item = Result<String>>) -> Option<Result<(i64, i64)>> {
+        let sums = parse_streamed(lines, parse_line).try_fold((0, 0), |(next, previous), nums| {
+            let nums = nums?;
+            Ok((
+                next + extrapolate_value(&nums, true),
+                previous + extrapolate_value(&nums, false),
+            ))
+        });
+        Some(sums)
+    }
 }
 
-fn calc_value_sum(file_path: &str, next: bool) -> i64 {
-    get_lines(file_path)
-        .iter()
-        .map(|line| split_whitespace::<i64>(line.as_str()))
-        .map(|nums| extrapolate_value(&nums, next))
-        .sum()
+pub fn calc_value_sum(histories: &[Vec<i64>], next: bool) -> i64 {
+    histories.iter().map(|nums| extrapolate_value(nums, next)).sum()
 }
 
-fn extrapolate_value(nums: &Vec<i64>, next: bool) -> i64 {
+fn extrapolate_value(nums: &[i64], next: bool) -> i64 {
     if nums.is_empty() {
         panic!("Cannot determine next value for empty series");
     }
 
-    let first = *nums.first().unwrap();
-    let last = *nums.last().unwrap();
-    if nums.iter().all(|&n| n == first) {
-        return first;
-    }
+    extrapolate(nums, if next { nums.len() as i64 } else { -1 })
+}
 
-    let diffs = nums
-        .iter()
-        .enumerate()
-        .skip(1)
-        .map(|(i, n)| n - nums.get(i - 1).unwrap())
-        .collect_vec();
-    let value_for_diffs = extrapolate_value(&diffs, next);
-    return if next { last + value_for_diffs } else { first - value_for_diffs };
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i64>>> {
+    input.lines().map(|line| parse_line(input, line)).collect()
+}
+
+fn parse_line(input: &str, line: &str) -> Result<Vec<i64>> {
+    match split_whitespace::<i64>(input, line)? {
+        nums if nums.is_empty() => Err(Error::at(input, line, "Empty history")),
+        nums => Ok(nums),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::calc_value_sum("d09/example", true);
+        let res = super::calc_value_sum(&super::parse_input(include_str!("example")).unwrap(), true);
         assert_eq!(114, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::calc_value_sum("d09/example", false);
+        let res = super::calc_value_sum(&super::parse_input(include_str!("example")).unwrap(), false);
         assert_eq!(2, res);
     }
 
     #[test]
     fn should_calc_correct_next_value() {
-        let res = super::extrapolate_value(&vec![10, 13, 16, 21, 30, 45], true);
+        let res = super::extrapolate_value(&[10, 13, 16, 21, 30, 45], true);
         assert_eq!(68, res);
     }
 
     #[test]
     fn should_calc_correct_first_value() {
-        let res = super::extrapolate_value(&vec![10, 13, 16, 21, 30, 45], false);
+        let res = super::extrapolate_value(&[10, 13, 16, 21, 30, 45], false);
         assert_eq!(5, res);
     }
 }
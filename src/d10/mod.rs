@@ -2,69 +2,50 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-use crate::d10::Direction::{DOWN, LEFT, RIGHT, UP};
-use crate::utils::{get_lines, part_end, part_start};
-
-type Coord = (i32, i32);
-type Grid = Vec<Vec<char>>;
-
-#[derive(Eq, PartialEq, Debug)]
-enum Direction {
-    LEFT,
-    RIGHT,
-    UP,
-    DOWN,
-}
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::Direction::{Down, Left, Right, Up};
+use crate::utils::{Coord, Direction, Grid};
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day10;
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", find_farthest_loop_tile_distance("d10/input"));
-    part_end(start);
-}
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Pipe Maze";
 
-fn part_two() {
-    let start = part_start(1);
-    println!("Result: {}", find_enclosed_tile_count("d10/input"));
-    part_end(start);
-}
+    type Input = (Coord, Grid<char>);
+    type PartOne = i32;
+    type PartTwo = i32;
 
-fn find_farthest_loop_tile_distance(file_path: &str) -> i32 {
-    let (start, grid) = parse_input(file_path);
-    let connected = find_connected_tiles(&grid, &start);
-    let mut cur_coord = connected.into_iter().next().unwrap();
-    let mut cur_char = get_unchecked(&grid, &cur_coord.0);
-    let mut steps = 1;
-
-    while cur_char != 'S' {
-        cur_coord = next(&grid, &cur_coord.0, &cur_coord.1);
-        cur_char = get_unchecked(&grid, &cur_coord.0);
-        steps += 1;
+    fn parse(&self, input: &str) -> Result<(Coord, Grid<char>)> {
+        parse_input(input)
     }
 
-    steps / 2
+    fn part_one(&self, input: &(Coord, Grid<char>)) -> Result<i32> {
+        find_farthest_loop_tile_distance(input)
+    }
+
+    fn part_two(&self, input: &(Coord, Grid<char>)) -> Result<i32> {
+        find_enclosed_tile_count(input)
+    }
 }
 
-fn find_enclosed_tile_count(file_path: &str) -> i32 {
-    let (start, grid) = parse_input(file_path);
-    let connected = find_connected_tiles(&grid, &start);
+pub fn find_farthest_loop_tile_distance((start, grid): &(Coord, Grid<char>)) -> Result<i32> {
+    let connected = find_connected_tiles(grid, start);
+    let tiles = find_loop_tiles(grid, start, connected.into_iter().next().unwrap())?;
+
+    Ok(tiles.len() as i32 / 2)
+}
+
+pub fn find_enclosed_tile_count((start, grid): &(Coord, Grid<char>)) -> Result<i32> {
+    let connected = find_connected_tiles(grid, start);
     let start_acts_as = determine_pipe_type(&connected);
-    let mut cur_coord = connected.into_iter().next().unwrap();
-    let mut cur_char = get_unchecked(&grid, &cur_coord.0);
-    let mut tiles: HashSet<Coord> = HashSet::new();
-    tiles.insert(start);
-
-    while cur_char != 'S' {
-        tiles.insert(cur_coord.0);
-        cur_coord = next(&grid, &cur_coord.0, &cur_coord.1);
-        cur_char = get_unchecked(&grid, &cur_coord.0);
-    }
+    let tiles: HashSet<Coord> = find_loop_tiles(grid, start, connected.into_iter().next().unwrap())?
+        .into_iter()
+        .collect();
 
-    grid.iter()
+    Ok(grid
+        .rows()
         .enumerate()
         .map(|(ri, row)| {
             let mut inside = false;
@@ -73,7 +54,7 @@ fn find_enclosed_tile_count(file_path: &str) -> i32 {
                 .map(|&c| if c == 'S' { start_acts_as } else { c })
                 .enumerate()
                 .fold(0, |acc, (ci, c)| {
-                    if !tiles.contains(&(ri as i32, ci as i32)) {
+                    if !tiles.contains(&(ri, ci)) {
                         return acc + inside as i32;
                     }
 
@@ -87,13 +68,11 @@ fn find_enclosed_tile_count(file_path: &str) -> i32 {
                     } else if ['7', 'J'].contains(&c) {
                         let open_char = (0..ci)
                             .rev()
-                            .map(|ci| row.get(ci).unwrap())
+                            .map(|ci| &row[ci])
                             .map(|&c| if c == 'S' { start_acts_as } else { c })
                             .find(|&c| c != '-')
                             .unwrap();
-                        if c == '7' && open_char == 'L' {
-                            inside = !inside;
-                        } else if c == 'J' && open_char == 'F' {
+                        if (c == '7' && open_char == 'L') || (c == 'J' && open_char == 'F') {
                             inside = !inside;
                         }
                     }
@@ -101,75 +80,67 @@ fn find_enclosed_tile_count(file_path: &str) -> i32 {
                     acc
                 })
         })
-        .sum()
+        .sum())
 }
 
-fn get(grid: &Grid, coord: &Coord) -> Option<char> {
-    if coord.0 < 0 || coord.1 < 0 {
-        return None;
+// Follows the loop from the start tile, beginning with the given connected tile, until we're back at the start
+fn find_loop_tiles(grid: &Grid<char>, start: &Coord, first: (Coord, Direction)) -> Result<Vec<Coord>> {
+    let mut tiles = vec![*start];
+    let mut cur_coord = first;
+    while cur_coord.0 != *start {
+        tiles.push(cur_coord.0);
+        cur_coord = next(grid, &cur_coord.0, cur_coord.1)?;
     }
-    grid.get(coord.0 as usize)
-        .and_then(|row| row.get(coord.1 as usize))
-        .map(|&c| c)
-}
 
-fn get_unchecked(grid: &Grid, coord: &Coord) -> char {
-    get(grid, coord).unwrap()
+    Ok(tiles)
 }
 
 // Determines the next tile based on current tile + direction of the step that got us in there
 // For example, if we moved into '-' by "moving left", we entered it from the right and
 // consequently have to move left next.
-fn next(grid: &Grid, pos: &Coord, prev_dir: &Direction) -> (Coord, Direction) {
-    let c = get_unchecked(grid, pos);
-    match (prev_dir, c) {
-        (LEFT, '-') => move_left(pos),
-        (LEFT, 'F') => move_down(pos),
-        (LEFT, 'L') => move_up(pos),
-        (RIGHT, '-') => move_right(pos),
-        (RIGHT, '7') => move_down(pos),
-        (RIGHT, 'J') => move_up(pos),
-        (UP, '|') => move_up(pos),
-        (UP, '7') => move_left(pos),
-        (UP, 'F') => move_right(pos),
-        (DOWN, '|') => move_down(pos),
-        (DOWN, 'J') => move_left(pos),
-        (DOWN, 'L') => move_right(pos),
-        (d, c) => unreachable!("impossible move: {}/{:?}", c, d),
-    }
+fn next(grid: &Grid<char>, pos: &Coord, prev_dir: Direction) -> Result<(Coord, Direction)> {
+    let c = grid[*pos];
+    let dir = match (prev_dir, c) {
+        (Left, '-') | (Up, '7') | (Down, 'J') => Left,
+        (Right, '-') | (Up, 'F') | (Down, 'L') => Right,
+        (Up, '|') | (Left, 'L') | (Right, 'J') => Up,
+        (Down, '|') | (Left, 'F') | (Right, '7') => Down,
+        (d, c) => {
+            let message = format!("The loop is broken, cannot move {:?} into {:?}", d, c);
+            return Err(Error::at_position(pos.0 + 1, pos.1 + 1, message));
+        }
+    };
+    let next = grid
+        .step(*pos, dir)
+        .ok_or_else(|| Error::new("The loop leaves the grid"))?;
+    Ok((next, dir))
 }
 
 // Determines the two connected tiles for a provided pipe position.
 // Should return exactly two coordinates and the relative direction from the provided position.
-fn find_connected_tiles(grid: &Grid, start: &Coord) -> Vec<(Coord, Direction)> {
-    let up_coord = move_up(start);
-    let right_coord = move_right(start);
-    let down_coord = move_down(start);
-    let left_coord = move_left(start);
-
-    let up = get(grid, &up_coord.0)
-        .filter(|c| ['|', '7', 'F'].contains(c))
-        .map(|_| up_coord);
-    let down = get(grid, &down_coord.0)
-        .filter(|c| ['|', 'J', 'L'].contains(c))
-        .map(|_| down_coord);
-    let right = get(grid, &right_coord.0)
-        .filter(|c| ['-', 'J', '7'].contains(c))
-        .map(|_| right_coord);
-    let left = get(grid, &left_coord.0)
-        .filter(|c| ['-', 'L', 'F'].contains(c))
-        .map(|_| left_coord);
-
-    [up, down, right, left].into_iter().filter_map(|x| x).collect_vec()
+fn find_connected_tiles(grid: &Grid<char>, start: &Coord) -> Vec<(Coord, Direction)> {
+    [Up, Down, Right, Left]
+        .into_iter()
+        .filter_map(|dir| {
+            let coord = grid.step(*start, dir)?;
+            let connecting = match dir {
+                Up => ['|', '7', 'F'],
+                Right => ['-', 'J', '7'],
+                Down => ['|', 'J', 'L'],
+                Left => ['-', 'L', 'F'],
+            };
+            connecting.contains(&grid[coord]).then_some((coord, dir))
+        })
+        .collect_vec()
 }
 
 // Based on the two connected pipes, we can determine what type of pipe it is.
 // Necessary for determining the role of the starting point, when calculating enclosed tiles.
-fn determine_pipe_type(connected: &Vec<(Coord, Direction)>) -> char {
-    let up = connected.iter().any(|(_, d)| *d == UP);
-    let right = connected.iter().any(|(_, d)| *d == RIGHT);
-    let down = connected.iter().any(|(_, d)| *d == DOWN);
-    let left = connected.iter().any(|(_, d)| *d == LEFT);
+fn determine_pipe_type(connected: &[(Coord, Direction)]) -> char {
+    let up = connected.iter().any(|(_, d)| *d == Up);
+    let right = connected.iter().any(|(_, d)| *d == Right);
+    let down = connected.iter().any(|(_, d)| *d == Down);
+    let left = connected.iter().any(|(_, d)| *d == Left);
 
     match (up, right, down, left) {
         (true, false, true, false) => '|',
@@ -182,72 +153,66 @@ fn determine_pipe_type(connected: &Vec<(Coord, Direction)>) -> char {
     }
 }
 
-fn move_left(pos: &Coord) -> (Coord, Direction) {
-    ((pos.0, pos.1 - 1), LEFT)
-}
-
-fn move_right(pos: &Coord) -> (Coord, Direction) {
-    ((pos.0, pos.1 + 1), RIGHT)
-}
-
-fn move_up(pos: &Coord) -> (Coord, Direction) {
-    ((pos.0 - 1, pos.1), UP)
-}
-
-fn move_down(pos: &Coord) -> (Coord, Direction) {
-    ((pos.0 + 1, pos.1), DOWN)
-}
-
-fn parse_input(file_path: &str) -> (Coord, Grid) {
-    let mut start: Coord = (0, 0);
-    let grid = get_lines(file_path)
+pub fn parse_input(input: &str) -> Result<(Coord, Grid<char>)> {
+    let grid = Grid::parse(input, input, |c| "|-LJ7F.S".contains(c).then_some(c))?;
+    let starts = grid
         .iter()
-        .enumerate()
-        .map(|(row, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(col, c)| {
-                    if c == 'S' {
-                        start = (row as i32, col as i32);
-                    }
-                    c
-                })
-                .collect_vec()
-        })
+        .filter(|&(_, &c)| c == 'S')
+        .map(|(pos, _)| pos)
         .collect_vec();
+    let &(ri, ci) = starts.first().ok_or_else(|| Error::new("There is no start tile"))?;
+    if let Some(&(ri, ci)) = starts.get(1) {
+        return Err(Error::at_position(ri + 1, ci + 1, "Second start tile"));
+    }
 
-    (start, grid)
+    let start = (ri, ci);
+    if find_connected_tiles(&grid, &start).len() != 2 {
+        let message = "The start tile has to be connected to exactly two pipes";
+        return Err(Error::at_position(ri + 1, ci + 1, message));
+    }
+    Ok((start, grid))
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::find_farthest_loop_tile_distance("d10/example_part1");
-        assert_eq!(8, res);
+        let res = super::find_farthest_loop_tile_distance(&super::parse_input(include_str!("example_part1")).unwrap());
+        assert_eq!(8, res.unwrap());
     }
 
     #[test]
     fn example_part_two_a() {
-        let res = super::find_enclosed_tile_count("d10/example_part2a");
-        assert_eq!(4, res);
+        let res = super::find_enclosed_tile_count(&super::parse_input(include_str!("example_part2a")).unwrap());
+        assert_eq!(4, res.unwrap());
     }
 
     #[test]
     fn example_part_two_b() {
-        let res = super::find_enclosed_tile_count("d10/example_part2b");
-        assert_eq!(4, res);
+        let res = super::find_enclosed_tile_count(&super::parse_input(include_str!("example_part2b")).unwrap());
+        assert_eq!(4, res.unwrap());
     }
 
     #[test]
     fn example_part_two_c() {
-        let res = super::find_enclosed_tile_count("d10/example_part2c");
-        assert_eq!(8, res);
+        let res = super::find_enclosed_tile_count(&super::parse_input(include_str!("example_part2c")).unwrap());
+        assert_eq!(8, res.unwrap());
     }
 
     #[test]
     fn example_part_two_d() {
-        let res = super::find_enclosed_tile_count("d10/example_part2d");
-        assert_eq!(10, res);
+        let res = super::find_enclosed_tile_count(&super::parse_input(include_str!("example_part2d")).unwrap());
+        assert_eq!(10, res.unwrap());
+    }
+
+    #[test]
+    fn should_locate_broken_loop() {
+        let error =
+            super::find_farthest_loop_tile_distance(&super::parse_input(".....\n.S-7.\n.|.|.\n.L-|.\n.....").unwrap())
+                .unwrap_err();
+        assert_eq!(
+            "line 4, column 4: The loop is broken, cannot move Right into '|'",
+            error.to_string()
+        );
     }
 }
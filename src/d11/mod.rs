@@ -1,28 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::utils::{get_lines, part_end, part_start};
+use crate::error::{Error, Result};
+use crate::solution::Solution;
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day11;
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", calc_shortest_galaxy_paths_sum("d11/input", 2));
-    part_end(start);
-}
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Cosmic Expansion";
+
+    type Input = Vec<(usize, usize)>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<(usize, usize)>> {
+        parse_input(input)
+    }
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", calc_shortest_galaxy_paths_sum("d11/input", 1_000_000));
-    part_end(start);
+    fn part_one(&self, input: &Vec<(usize, usize)>) -> Result<usize> {
+        Ok(calc_shortest_galaxy_paths_sum(input, 2))
+    }
+
+    fn part_two(&self, input: &Vec<(usize, usize)>) -> Result<usize> {
+        Ok(calc_shortest_galaxy_paths_sum(input, 1_000_000))
+    }
 }
 
-fn calc_shortest_galaxy_paths_sum(file_path: &str, growth_factor: usize) -> usize {
-    let galaxy_coords = parse_input_and_expand(file_path, growth_factor);
+pub fn calc_shortest_galaxy_paths_sum(galaxies: &[(usize, usize)], growth_factor: usize) -> usize {
+    let galaxy_coords = expand(galaxies, growth_factor);
     let mut sum = 0;
     for (i, &(ri1, ci1)) in galaxy_coords.iter().enumerate() {
         for &(ri2, ci2) in galaxy_coords.iter().skip(i + 1) {
@@ -32,56 +39,67 @@ fn calc_shortest_galaxy_paths_sum(file_path: &str, growth_factor: usize) -> usiz
     sum
 }
 
-fn parse_input_and_expand(file_path: &str, growth_factor: usize) -> Vec<(usize, usize)> {
-    let mut empty_row_add = 0;
-    let lines = get_lines(file_path);
+fn expand(galaxies: &[(usize, usize)], growth_factor: usize) -> Vec<(usize, usize)> {
+    // Determine which row and column had how many empty rows and columns in front of it...
+    let empty_row_add_by_row = count_empty_in_front(galaxies.iter().map(|&(ri, _)| ri).collect());
+    let empty_col_add_by_col = count_empty_in_front(galaxies.iter().map(|&(_, ci)| ci).collect());
 
-    let mut galaxy_coords = lines
+    // ...and then expand indices accordingly
+    galaxies
         .iter()
+        .map(|&(ri, ci)| {
+            (
+                ri + empty_row_add_by_row.get(&ri).unwrap() * (growth_factor - 1),
+                ci + empty_col_add_by_col.get(&ci).unwrap() * (growth_factor - 1),
+            )
+        })
+        .collect_vec()
+}
+
+fn count_empty_in_front(used: HashSet<usize>) -> HashMap<usize, usize> {
+    let count = used.iter().max().map_or(0, |&max| max + 1);
+    (0..count).fold(HashMap::new(), |mut acc, i| {
+        let empty = !used.contains(&i);
+        let prev = if i == 0 { 0 } else { *acc.get(&(i - 1)).unwrap() };
+        acc.insert(i, prev + empty as usize);
+        acc
+    })
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<(usize, usize)>> {
+    if let Some((line, i)) = input
+        .lines()
+        .find_map(|line| line.find(|c| c != '.' && c != '#').map(|i| (line, i)))
+    {
+        return Err(Error::at(input, &line[i..], "Expected '.' or '#'"));
+    }
+
+    Ok(input
+        .lines()
         .enumerate()
         .flat_map(|(ri, line)| {
-            let row_gal_coords = line
-                .chars()
+            line.chars()
                 .enumerate()
                 .filter(|&(_, c)| c == '#')
-                .map(|(ci, _)| (ri + empty_row_add * (growth_factor - 1), ci))
-                .collect_vec();
-            // In case this row is empty, next galaxy rows are moved by +1
-            empty_row_add += row_gal_coords.is_empty() as usize;
-            row_gal_coords
+                .map(|(ci, _)| (ri, ci))
+                .collect_vec()
         })
-        .collect_vec();
-
-    // Determine which column had how many empty columns in front of it...
-    let col_count = lines.first().unwrap().len();
-    let empty_col_add_by_col = (0..col_count).fold(HashMap::new(), |mut acc, ci| {
-        let empty = galaxy_coords.iter().all(|&(_, gci)| gci != ci);
-        let prev = if ci == 0 { 0 } else { *acc.get(&(ci - 1)).unwrap() };
-        acc.insert(ci, prev + empty as usize);
-        acc
-    });
-
-    // ...and then expand column indices accordingly
-    galaxy_coords.iter_mut().for_each(|x| {
-        x.1 += empty_col_add_by_col.get(&x.1).unwrap() * (growth_factor - 1);
-    });
-
-    galaxy_coords
+        .collect_vec())
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::calc_shortest_galaxy_paths_sum("d11/example", 2);
+        let res = super::calc_shortest_galaxy_paths_sum(&super::parse_input(include_str!("example")).unwrap(), 2);
         assert_eq!(374, res);
     }
 
     #[test]
     fn example_part_two() {
-        let mut res = super::calc_shortest_galaxy_paths_sum("d11/example", 10);
+        let mut res = super::calc_shortest_galaxy_paths_sum(&super::parse_input(include_str!("example")).unwrap(), 10);
         assert_eq!(1030, res);
-        res = super::calc_shortest_galaxy_paths_sum("d11/example", 100);
+        res = super::calc_shortest_galaxy_paths_sum(&super::parse_input(include_str!("example")).unwrap(), 100);
         assert_eq!(8410, res);
     }
 }
@@ -1,60 +1,71 @@
-use std::collections::HashMap;
-
 use itertools::Itertools;
 
-use crate::utils::{get_lines, part_end, part_start};
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{parse_at, parse_streamed, split_once_at, Memo};
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day12;
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", compute_count_sum("d12/input"));
-    part_end(start);
-}
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hot Springs";
+
+    type Input = Vec<(String, Vec<usize>)>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<(String, Vec<usize>)>> {
+        parse_input(input)
+    }
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", compute_unfolded_count_sum("d12/input"));
-    part_end(start);
+    fn part_one(&self, input: &Vec<(String, Vec<usize>)>) -> Result<usize> {
+        Ok(compute_count_sum(input))
+    }
+
+    fn part_two(&self, input: &Vec<(String, Vec<usize>)>) -> Result<usize> {
+        Ok(compute_unfolded_count_sum(input))
+    }
+
+    fn solve_lines(&self, lines: &mut dyn Iterator<Item = Result<String>>) -> Option<Result<(usize, usize)>> {
+        // A memo per record keeps the memory constant, records hardly share any rests anyway
+        let sums = parse_streamed(lines, parse_line).try_fold((0, 0), |(folded, unfolded), record| {
+            let record = record?;
+            Ok((
+                folded + compute_count_sum(std::slice::from_ref(&record)),
+                unfolded + compute_count_sum(&[unfold(&record)]),
+            ))
+        });
+        Some(sums)
+    }
 }
 
-fn compute_count_sum(file_path: &str) -> usize {
-    let mut cache: HashMap<String, usize> = HashMap::new();
-    parse_input(file_path)
+// Arrangement counts by remaining row and damage counts, borrowed from the records
+type RowMemo<'a> = Memo<(&'a str, &'a [usize]), usize>;
+
+pub fn compute_count_sum(records: &[(String, Vec<usize>)]) -> usize {
+    let mut memo = RowMemo::new();
+    records
         .iter()
-        .map(|(row, counts)| get_or_compute_for_row(&skip_and_trim(row, 0), counts, &mut cache))
+        .map(|(row, counts)| get_or_compute_for_row(skip_and_trim(row, 0), counts, &mut memo))
         .sum()
 }
 
-fn compute_unfolded_count_sum(file_path: &str) -> usize {
-    let mut cache: HashMap<String, usize> = HashMap::new();
-    parse_input(file_path)
-        .iter()
-        .map(|(row, counts)| {
-            (
-                [row; 5].iter().join("?"),
-                [counts; 5].into_iter().flatten().map(|&n| n).collect_vec(),
-            )
-        })
-        .map(|(row, counts)| get_or_compute_for_row(&skip_and_trim(&row, 0), &counts, &mut cache))
-        .sum()
+pub fn compute_unfolded_count_sum(records: &[(String, Vec<usize>)]) -> usize {
+    compute_count_sum(&records.iter().map(unfold).collect_vec())
 }
 
-fn get_or_compute_for_row(r: &str, counts: &Vec<usize>, cache: &mut HashMap<String, usize>) -> usize {
-    let cache_key = format!("{}_{}", r, counts.iter().join("_"));
-    if cache.contains_key(&cache_key) {
-        return *cache.get(&cache_key).unwrap();
-    }
+fn unfold((row, counts): &(String, Vec<usize>)) -> (String, Vec<usize>) {
+    (
+        [row; 5].iter().join("?"),
+        [counts; 5].into_iter().flatten().copied().collect_vec(),
+    )
+}
 
-    let result = compute_for_row(r, counts, cache);
-    cache.insert(cache_key, result);
-    result
+fn get_or_compute_for_row<'a>(r: &'a str, counts: &'a [usize], memo: &mut RowMemo<'a>) -> usize {
+    memo.get_or_compute((r, counts), |memo| compute_for_row(r, counts, memo))
 }
 
-fn compute_for_row(r: &str, counts: &Vec<usize>, cache: &mut HashMap<String, usize>) -> usize {
+fn compute_for_row<'a>(r: &'a str, counts: &'a [usize], memo: &mut RowMemo<'a>) -> usize {
     // When the full row was already processed, there should be no damage count left
     if r.is_empty() {
         return counts.is_empty() as usize;
@@ -78,7 +89,7 @@ fn compute_for_row(r: &str, counts: &Vec<usize>, cache: &mut HashMap<String, usi
         return 0;
     }
 
-    let followed_by_damaged = r.chars().skip(c).next().unwrap() == '#';
+    let followed_by_damaged = r.chars().nth(c).unwrap() == '#';
     let next_count_has_operational = r[0..c].contains('.');
     // See below in which cases this is relevant
     let cannot_be_damaged = followed_by_damaged || next_count_has_operational;
@@ -90,127 +101,140 @@ fn compute_for_row(r: &str, counts: &Vec<usize>, cache: &mut HashMap<String, usi
             if cannot_be_damaged {
                 return 0;
             }
-            let counts_without_first = &counts.into_iter().skip(1).map(|&n| n).collect_vec();
-            get_or_compute_for_row(&skip_and_trim(r, c + 1), counts_without_first, cache)
+            get_or_compute_for_row(skip_and_trim(r, c + 1), &counts[1..], memo)
         }
         '?' => {
-            let result_when_not_placed = get_or_compute_for_row(&skip_and_trim(r, 1), counts, cache);
+            let result_when_not_placed = get_or_compute_for_row(skip_and_trim(r, 1), counts, memo);
             // Next '?' must mean '.' in such cases: "??. 3" or "???# 3"
             if cannot_be_damaged {
                 return result_when_not_placed;
             }
-            let counts_without_first = &counts.into_iter().skip(1).map(|&n| n).collect_vec();
-            let result_when_placed = get_or_compute_for_row(&skip_and_trim(r, c + 1), counts_without_first, cache);
+            let result_when_placed = get_or_compute_for_row(skip_and_trim(r, c + 1), &counts[1..], memo);
 
-            return result_when_placed + result_when_not_placed;
+            result_when_placed + result_when_not_placed
         }
         _ => unreachable!("Impossible char: {first_char}"),
     }
 }
 
-fn skip_and_trim(row: &str, skip: usize) -> String {
-    String::from(row[skip..].trim_matches(|c| c == '.'))
+fn skip_and_trim(row: &str, skip: usize) -> &str {
+    row[skip..].trim_matches('.')
 }
 
-fn parse_input(file_path: &str) -> Vec<(String, Vec<usize>)> {
-    get_lines(file_path)
-        .iter()
-        .map(|line| line.split_once(' ').unwrap())
-        .map(|(s, nums)| {
-            (
-                String::from(s),
-                nums.split(',').map(|n| n.parse::<usize>().unwrap()).collect_vec(),
-            )
-        })
-        .collect_vec()
+pub fn parse_input(input: &str) -> Result<Vec<(String, Vec<usize>)>> {
+    input.lines().map(|line| parse_line(input, line)).collect()
+}
+
+fn parse_line(input: &str, line: &str) -> Result<(String, Vec<usize>)> {
+    let (s, nums) = split_once_at(input, line, " ")?;
+    if let Some(i) = s.find(|c| !['.', '#', '?'].contains(&c)) {
+        return Err(Error::at(input, &s[i..], "Expected '.', '#' or '?'"));
+    }
+    Ok((
+        String::from(s),
+        nums.split(',')
+            .map(|n| parse_at(input, n))
+            .collect::<Result<Vec<usize>>>()?,
+    ))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use crate::utils::Memo;
 
     #[test]
     fn example_part_one() {
-        let res = super::compute_count_sum("d12/example");
+        let res = super::compute_count_sum(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(21, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::compute_unfolded_count_sum("d12/example");
+        let res = super::compute_unfolded_count_sum(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(525152, res);
     }
 
     // Investigated some examples during debugging, so I kept them:
     #[test]
     fn test_case_1() {
-        let res = super::compute_for_row("?#?#?#?#?#?#?#?", &vec![1, 3, 1, 6], &mut HashMap::new());
+        let res = super::compute_for_row("?#?#?#?#?#?#?#?", &[1, 3, 1, 6], &mut Memo::new());
         assert_eq!(1, res);
     }
 
     #[test]
     fn test_case_2() {
-        let res = super::compute_for_row("????.######..#####.", &vec![1, 6, 5], &mut HashMap::new());
+        let res = super::compute_for_row("????.######..#####.", &[1, 6, 5], &mut Memo::new());
         assert_eq!(4, res);
     }
 
     #[test]
     fn test_case_3() {
-        let res = super::compute_for_row("?###????????", &vec![3, 2, 1], &mut HashMap::new());
+        let res = super::compute_for_row("?###????????", &[3, 2, 1], &mut Memo::new());
         assert_eq!(10, res);
     }
 
     #[test]
     fn test_case_4() {
-        let res = super::compute_for_row("??????#?#?#??", &vec![2, 2, 6], &mut HashMap::new());
+        let res = super::compute_for_row("??????#?#?#??", &[2, 2, 6], &mut Memo::new());
         assert_eq!(1, res);
     }
 
     #[test]
     fn test_case_5() {
-        let res = super::compute_for_row("??????.?##?#??#?????", &vec![3, 1, 12], &mut HashMap::new());
+        let res = super::compute_for_row("??????.?##?#??#?????", &[3, 1, 12], &mut Memo::new());
         assert_eq!(6, res);
     }
 
     #[test]
     fn test_case_6() {
-        let res = super::compute_for_row("??#?.#??.?#?.???", &vec![2, 2, 2, 1, 1], &mut HashMap::new());
+        let res = super::compute_for_row("??#?.#??.?#?.???", &[2, 2, 2, 1, 1], &mut Memo::new());
         assert_eq!(4, res);
     }
 
     #[test]
     fn test_case_7() {
-        let res = super::compute_for_row("??#?????#?#?????", &vec![1, 5, 1, 3, 1], &mut HashMap::new());
+        let res = super::compute_for_row("??#?????#?#?????", &[1, 5, 1, 3, 1], &mut Memo::new());
         assert_eq!(2, res);
     }
 
     #[test]
     fn test_case_8() {
-        let res = super::compute_for_row("?????#??#???##?.?#??", &vec![1, 6, 3, 3], &mut HashMap::new());
+        let res = super::compute_for_row("?????#??#???##?.?#??", &[1, 6, 3, 3], &mut Memo::new());
         assert_eq!(28, res);
     }
 
     #[test]
     fn test_case_9() {
-        let res = super::compute_for_row("?#..??#.????####?##?", &vec![2, 3, 3, 4, 3], &mut HashMap::new());
+        let res = super::compute_for_row("?#..??#.????####?##?", &[2, 3, 3, 4, 3], &mut Memo::new());
         assert_eq!(1, res);
     }
 
     #[test]
     fn test_case_10() {
-        let res = super::compute_for_row("??.?????????", &vec![2, 1, 2, 1], &mut HashMap::new());
+        let res = super::compute_for_row("??.?????????", &[2, 1, 2, 1], &mut Memo::new());
         assert_eq!(21, res);
     }
 
     #[test]
     fn test_case_11() {
-        let res = super::compute_for_row("?????????", &vec![1, 2, 1], &mut HashMap::new());
+        let res = super::compute_for_row("?????????", &[1, 2, 1], &mut Memo::new());
         assert_eq!(20, res);
     }
 
     #[test]
     fn test_case_12() {
-        let res = super::compute_for_row("?.?????????", &vec![2, 1, 2, 1], &mut HashMap::new());
+        let res = super::compute_for_row("?.?????????", &[2, 1, 2, 1], &mut Memo::new());
         assert_eq!(1, res);
     }
+
+    #[test]
+    fn should_report_cache_stats_of_streamed_lines() {
+        use crate::solution::DynSolution;
+
+        let mut lines = include_str!("example").lines().map(|line| Ok(line.to_string()));
+        let report = super::Day12.run_lines(&mut lines, &[1, 2]).unwrap();
+        assert!(report.single_pass);
+        assert_eq!(Ok(String::from("525152")), report.parts[1].answer);
+        assert!(report.parts[0].cache.lookups() > 0);
+    }
 }
@@ -1,34 +1,37 @@
-use itertools::Itertools;
+use crate::error::Result;
+use crate::solution::Solution;
+use crate::utils::{blocks, Grid};
 
-use crate::utils::{get_lines, part_end, part_start};
+pub struct Day13;
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Point of Incidence";
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", calc_grid_points("d13/input", 0));
-    part_end(start);
-}
+    type Input = Vec<Grid<char>>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Grid<char>>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Vec<Grid<char>>) -> Result<usize> {
+        Ok(calc_grid_points(input, 0))
+    }
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", calc_grid_points("d13/input", 1));
-    part_end(start);
+    fn part_two(&self, input: &Vec<Grid<char>>) -> Result<usize> {
+        Ok(calc_grid_points(input, 1))
+    }
 }
 
-fn calc_grid_points(file_path: &str, expected_err: usize) -> usize {
-    parse_input(file_path)
+pub fn calc_grid_points(grids: &[Grid<char>], expected_err: usize) -> usize {
+    grids
         .iter()
         .map(|grid| {
-            let col_count = grid.first().unwrap().len();
-            let col_points = (1..col_count)
-                .find(|&i| is_symmetric_by_cols(grid, i, expected_err))
-                .unwrap_or(0);
-            let row_points = (1..grid.len())
-                .find(|&i| is_symmetric_by_rows(grid, i, expected_err))
+            // Columns of the grid are the rows of its transposition
+            let col_points = find_row_reflection(&grid.transpose(), expected_err).unwrap_or(0);
+            let row_points = find_row_reflection(grid, expected_err)
                 .map(|p| p * 100usize)
                 .unwrap_or(0);
             col_points + row_points
@@ -36,30 +39,18 @@ fn calc_grid_points(file_path: &str, expected_err: usize) -> usize {
         .sum()
 }
 
-fn is_symmetric_by_cols(grid: &Vec<Vec<char>>, col: usize, expected_err: usize) -> bool {
-    let col_count = grid.first().unwrap().len();
-    let radius = col.min(col_count - col);
-    let mut errors = 0usize;
-    for line in grid {
-        for ci in 1..=radius {
-            errors += (line[col - ci] != line[col + ci - 1]) as usize;
-            if errors > expected_err {
-                return false;
-            }
-        }
-    }
-
-    errors == expected_err
+fn find_row_reflection(grid: &Grid<char>, expected_err: usize) -> Option<usize> {
+    (1..grid.row_count()).find(|&i| is_symmetric_by_rows(grid, i, expected_err))
 }
 
-fn is_symmetric_by_rows(grid: &Vec<Vec<char>>, row: usize, expected_err: usize) -> bool {
-    let radius = row.min(grid.len() - row);
+fn is_symmetric_by_rows(grid: &Grid<char>, row: usize, expected_err: usize) -> bool {
+    let radius = row.min(grid.row_count() - row);
     let mut errors = 0usize;
     for ri in 1..=radius {
-        let row1 = grid.get(row - ri).unwrap();
-        let row2 = grid.get(row + ri - 1).unwrap();
-        for (ci, c) in row1.iter().enumerate() {
-            errors += (c != row2.get(ci).unwrap()) as usize;
+        let row1 = grid.row(row - ri);
+        let row2 = grid.row(row + ri - 1);
+        for (c1, c2) in row1.iter().zip(row2) {
+            errors += (c1 != c2) as usize;
             if errors > expected_err {
                 return false;
             }
@@ -69,28 +60,29 @@ fn is_symmetric_by_rows(grid: &Vec<Vec<char>>, row: usize, expected_err: usize)
     errors == expected_err
 }
 
-fn parse_input(file_path: &str) -> Vec<Vec<Vec<char>>> {
-    get_lines(file_path).iter().fold(vec![vec![]], |mut acc, line| {
-        if line.is_empty() {
-            acc.push(Vec::new());
-        } else {
-            acc.last_mut().unwrap().push(line.chars().collect_vec());
-        }
-        acc
-    })
+pub fn parse_input(input: &str) -> Result<Vec<Grid<char>>> {
+    blocks(input)
+        .map(|pattern| Grid::parse(input, pattern, |c| ['.', '#'].contains(&c).then_some(c)))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::calc_grid_points("d13/example", 0);
+        let res = super::calc_grid_points(&super::parse_input(include_str!("example")).unwrap(), 0);
         assert_eq!(405, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::calc_grid_points("d13/example", 1);
+        let res = super::calc_grid_points(&super::parse_input(include_str!("example")).unwrap(), 1);
         assert_eq!(400, res);
     }
+
+    #[test]
+    fn should_locate_invalid_char_in_second_pattern() {
+        let error = super::parse_input("#.\n..\n\n..\n.x").unwrap_err();
+        assert_eq!("line 5, column 2: Unexpected 'x'", error.to_string());
+    }
 }
@@ -1,88 +1,68 @@
-use std::collections::HashMap;
-
-use itertools::Itertools;
-
-use crate::d14::Direction::{EAST, NORTH, SOUTH, WEST};
-use crate::utils::{part_end, part_start};
+use crate::error::Result;
+use crate::solution::Solution;
+use crate::utils::Direction::{Down, Left, Right, Up};
+use crate::utils::{iterate_with_cycle, Direction, Grid};
 
 const LOOSE: u8 = b'O';
 const FIXED: u8 = b'#';
 const EMPTY: u8 = b'.';
 
-#[derive(PartialEq, Eq)]
-enum Direction {
-    NORTH,
-    WEST,
-    SOUTH,
-    EAST,
-}
+pub struct Day14;
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Parabolic Reflector Dish";
 
-fn part_one() {
-    let start = part_start(1);
-    let mut bytes = include_bytes!("input").clone();
-    println!("Result: {}", compute_load_after_single_north_tilt(&mut bytes));
-    part_end(start);
-}
+    type Input = Grid<u8>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-fn part_two() {
-    let start = part_start(2);
-    let mut bytes = include_bytes!("input").clone();
-    println!("Result: {}", compute_load_after_billion_cycles(&mut bytes));
-    part_end(start);
+    fn parse(&self, input: &str) -> Result<Grid<u8>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Grid<u8>) -> Result<usize> {
+        // Tilting moves the rocks, so each part works on its own copy of the platform
+        Ok(compute_load_after_single_north_tilt(&mut input.clone()))
+    }
+
+    fn part_two(&self, input: &Grid<u8>) -> Result<usize> {
+        Ok(compute_load_after_billion_cycles(&mut input.clone()))
+    }
 }
 
-fn compute_load_after_single_north_tilt(bytes: &mut [u8]) -> usize {
-    let (row_count, col_count) = size(bytes);
-    tilt(bytes, row_count, col_count, NORTH);
-    calc_north_load(bytes, row_count, col_count)
+pub fn compute_load_after_single_north_tilt(grid: &mut Grid<u8>) -> usize {
+    tilt(grid, Up);
+    calc_north_load(grid)
 }
 
-fn compute_load_after_billion_cycles(bytes: &mut [u8]) -> usize {
-    let (row_count, col_count) = size(bytes);
-    let mut cache: HashMap<String, usize> = HashMap::new();
-    let mut i = 0;
-    let mut cycle_found = false;
-    while i < 1_000_000_000 {
-        tilt(bytes, row_count, col_count, NORTH);
-        tilt(bytes, row_count, col_count, WEST);
-        tilt(bytes, row_count, col_count, SOUTH);
-        tilt(bytes, row_count, col_count, EAST);
-        let cache_key = bytes.iter().map(|&b| b as char).join("");
-        if cycle_found {
-            i += 1;
-        } else if cache.contains_key(&cache_key) {
-            let cycle_start = cache.get(&cache_key).unwrap();
-            let cycle_len = i - cycle_start;
-            let div = (1_000_000_000 - cycle_start) / cycle_len;
-            i = cycle_start + div * cycle_len + 1;
-            cycle_found = true;
-        } else {
-            cache.insert(cache_key, i);
-            i += 1;
+pub fn compute_load_after_billion_cycles(grid: &mut Grid<u8>) -> usize {
+    let spun = iterate_with_cycle(grid.clone(), 1_000_000_000, |grid| {
+        let mut grid = grid.clone();
+        for dir in [Up, Left, Down, Right] {
+            tilt(&mut grid, dir);
         }
-    }
+        grid
+    });
+    *grid = spun.state;
 
-    calc_north_load(bytes, row_count, col_count)
+    calc_north_load(grid)
 }
 
-fn tilt(bytes: &mut [u8], row_count: usize, col_count: usize, dir: Direction) {
+fn tilt(grid: &mut Grid<u8>, dir: Direction) {
+    let (row_count, col_count) = (grid.row_count(), grid.col_count());
     let mut cur_fixed;
-    let horizontal = dir == EAST || dir == WEST;
-    let inc_fixed = dir == NORTH || dir == WEST;
+    let horizontal = dir.is_horizontal();
+    let inc_fixed = dir == Up || dir == Left;
 
     let mut process_char = |ri: usize, ci: usize, cur: &mut usize| {
-        let c = bytes[ri * (col_count + 1) + ci];
+        let c = grid[(ri, ci)];
         match c {
             LOOSE => {
-                bytes[ri * (col_count + 1) + ci] = EMPTY;
+                grid[(ri, ci)] = EMPTY;
                 let next_ri = if horizontal { ri } else { *cur };
                 let next_ci = if horizontal { *cur } else { ci };
-                bytes[next_ri * (col_count + 1) + next_ci] = LOOSE;
+                grid[(next_ri, next_ci)] = LOOSE;
                 *cur = if inc_fixed { *cur + 1 } else { (*cur).max(1) - 1 };
             }
             FIXED => {
@@ -95,7 +75,7 @@ fn tilt(bytes: &mut [u8], row_count: usize, col_count: usize, dir: Direction) {
     };
 
     match dir {
-        NORTH => {
+        Up => {
             for ci in 0..col_count {
                 cur_fixed = 0;
                 for ri in 0..row_count {
@@ -103,7 +83,7 @@ fn tilt(bytes: &mut [u8], row_count: usize, col_count: usize, dir: Direction) {
                 }
             }
         }
-        WEST => {
+        Left => {
             for ri in 0..row_count {
                 cur_fixed = 0;
                 for ci in 0..col_count {
@@ -111,7 +91,7 @@ fn tilt(bytes: &mut [u8], row_count: usize, col_count: usize, dir: Direction) {
                 }
             }
         }
-        SOUTH => {
+        Down => {
             for ci in 0..col_count {
                 cur_fixed = row_count - 1;
                 for ri in (0..row_count).rev() {
@@ -119,7 +99,7 @@ fn tilt(bytes: &mut [u8], row_count: usize, col_count: usize, dir: Direction) {
                 }
             }
         }
-        EAST => {
+        Right => {
             for ri in 0..row_count {
                 cur_fixed = col_count - 1;
                 for ci in (0..col_count).rev() {
@@ -130,38 +110,46 @@ fn tilt(bytes: &mut [u8], row_count: usize, col_count: usize, dir: Direction) {
     }
 }
 
-fn calc_north_load(bytes: &[u8], row_count: usize, col_count: usize) -> usize {
-    let mut res = 0usize;
-    for ri in 0..row_count {
-        for ci in 0..col_count {
-            if bytes[ri * (col_count + 1) + ci] == LOOSE {
-                res += row_count - ri;
-            }
-        }
-    }
-
-    res
+fn calc_north_load(grid: &Grid<u8>) -> usize {
+    grid.iter()
+        .filter(|&(_, &c)| c == LOOSE)
+        .map(|((ri, _), _)| grid.row_count() - ri)
+        .sum()
 }
 
-fn size(bytes: &[u8]) -> (usize, usize) {
-    let col_count = bytes.iter().position(|&b| b == b'\n').unwrap();
-    let row_count = bytes.iter().filter(|&&b| b == b'\n').count() + 1;
-    (row_count, col_count)
+pub fn parse_input(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(input, input, |c| {
+        (c.is_ascii() && [LOOSE, FIXED, EMPTY].contains(&(c as u8))).then_some(c as u8)
+    })
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let mut bytes = include_bytes!("example").clone();
-        let res = super::compute_load_after_single_north_tilt(&mut bytes);
+        let mut grid = super::parse_input(include_str!("example")).unwrap();
+        let res = super::compute_load_after_single_north_tilt(&mut grid);
         assert_eq!(136, res);
     }
 
     #[test]
     fn example_part_two() {
-        let mut bytes = include_bytes!("example").clone();
-        let res = super::compute_load_after_billion_cycles(&mut bytes);
+        let mut grid = super::parse_input(include_str!("example")).unwrap();
+        let res = super::compute_load_after_billion_cycles(&mut grid);
         assert_eq!(64, res);
     }
+
+    #[test]
+    fn should_parse_crlf_grid() {
+        use crate::solution::Solution;
+
+        let input = include_str!("example").replace('\n', "\r\n") + "\r\n";
+        assert_eq!(136, super::Day14.solve_part_one(&input).unwrap());
+    }
+
+    #[test]
+    fn should_reject_non_ascii_rock() {
+        let error = super::parse_input("O\u{14f}.\n...").unwrap_err();
+        assert_eq!("line 1, column 2: Unexpected '\u{14f}'", error.to_string());
+    }
 }
@@ -1,44 +1,52 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
+use crate::error::{Error, Result};
+use crate::solution::Solution;
 
-use crate::utils::{get_lines, part_end, part_start};
+pub struct Day15;
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Lens Library";
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", compute_hash_sum("d15/input"));
-    part_end(start);
-}
+    type Input = Vec<String>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> Result<u32> {
+        Ok(compute_hash_sum(input))
+    }
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", compute_focus_power_sum("d15/input"));
-    part_end(start);
+    fn part_two(&self, input: &Vec<String>) -> Result<u32> {
+        Ok(compute_focus_power_sum(input))
+    }
 }
 
-fn compute_hash_sum(file_path: &str) -> u32 {
-    parse_input(file_path).iter().map(|p| hash(p.as_str())).sum()
+pub fn compute_hash_sum(ops: &[String]) -> u32 {
+    ops.iter().map(|p| hash(p.as_str())).sum()
 }
 
-fn compute_focus_power_sum(file_path: &str) -> u32 {
-    let ops = parse_input(file_path);
+pub fn compute_focus_power_sum(ops: &[String]) -> u32 {
     let mut boxes: HashMap<u8, Vec<(String, u8)>> = HashMap::new();
 
     for op in ops {
         if op.ends_with('-') {
             let label = &op[..op.len() - 1];
             let b = hash(label) as u8;
-            boxes.get_mut(&b).map(|list| list.retain(|(s, _)| s != label));
+            if let Some(list) = boxes.get_mut(&b) {
+                list.retain(|(s, _)| s != label)
+            }
         } else {
             let label = &op[..op.len() - 2];
             let focal_length = op.chars().last().unwrap().to_digit(10).unwrap() as u8;
             let b = hash(label) as u8;
-            if boxes.contains_key(&b) {
+            if let std::collections::hash_map::Entry::Vacant(e) = boxes.entry(b) {
+                e.insert(vec![(String::from(label), focal_length)]);
+            } else {
                 let lenses = boxes.get_mut(&b).unwrap();
                 let existing = lenses.iter_mut().find(|(s, _)| s == label);
                 if let Some(lense) = existing {
@@ -46,8 +54,6 @@ fn compute_focus_power_sum(file_path: &str) -> u32 {
                 } else {
                     lenses.push((String::from(label), focal_length));
                 }
-            } else {
-                boxes.insert(b, vec![(String::from(label), focal_length)]);
             }
         }
     }
@@ -68,24 +74,47 @@ fn hash(target: &str) -> u32 {
     target.chars().fold(0, |acc, c| ((acc + c as u32) * 17) % 256)
 }
 
-fn parse_input(file_path: &str) -> Vec<String> {
-    get_lines(file_path)
-        .iter()
-        .flat_map(|line| line.split(',').map(|x| String::from(x)))
-        .collect_vec()
+pub fn parse_input(input: &str) -> Result<Vec<String>> {
+    input
+        .lines()
+        .flat_map(|line| line.split(','))
+        .map(|op| {
+            let label = op.trim_end_matches(|c: char| c == '-' || c == '=' || c.is_ascii_digit());
+            let valid_label = !label.is_empty() && label.chars().all(|c| c.is_ascii_lowercase());
+            let valid_op = match &op[label.len()..] {
+                "-" => true,
+                focal_length => {
+                    focal_length.len() == 2 && focal_length.starts_with('=') && !focal_length.ends_with('0')
+                }
+            };
+            if valid_label && valid_op {
+                Ok(String::from(op))
+            } else {
+                Err(Error::at(input, op, format!("Invalid step {op:?}")))
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::compute_hash_sum("d15/example");
+        let res = super::compute_hash_sum(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(1320, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::compute_focus_power_sum("d15/example");
+        let res = super::compute_focus_power_sum(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(145, res);
     }
+
+    #[test]
+    fn should_ignore_line_ending_and_bom() {
+        use crate::solution::Solution;
+
+        let input = format!("\u{feff}{}\r\n", include_str!("example"));
+        assert_eq!(1320, super::Day15.solve_part_one(&input).unwrap());
+    }
 }
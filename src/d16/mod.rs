@@ -1,74 +1,66 @@
 use std::collections::HashSet;
 
-use crate::d16::Direction::{DOWN, LEFT, RIGHT, UP};
-use crate::utils::{part_end, part_start};
-
-#[derive(PartialEq, Eq, Copy, Clone, Hash)]
-enum Direction {
-    UP,
-    RIGHT,
-    DOWN,
-    LEFT,
-}
+use crate::error::Result;
+use crate::solution::Solution;
+use crate::utils::Direction::{Down, Left, Right, Up};
+use crate::utils::{Coord, Direction, Grid};
 
-type Beam = (i32, i32, Direction);
+type Beam = (Coord, Direction);
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day16;
 
-fn part_one() {
-    let start = part_start(1);
-    println!(
-        "Result: {}",
-        compute_energized_tile_count_from_top_left(include_bytes!("input"))
-    );
-    part_end(start);
-}
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "The Floor Will Be Lava";
+
+    type Input = Grid<u8>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-fn part_two() {
-    let start = part_start(2);
-    println!(
-        "Result: {}",
-        compute_max_energized_tile_count_from_any_start(include_bytes!("input"))
-    );
-    part_end(start);
+    fn parse(&self, input: &str) -> Result<Grid<u8>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Grid<u8>) -> Result<usize> {
+        Ok(compute_energized_tile_count_from_top_left(input))
+    }
+
+    fn part_two(&self, input: &Grid<u8>) -> Result<usize> {
+        Ok(compute_max_energized_tile_count_from_any_start(input))
+    }
 }
 
-fn compute_energized_tile_count_from_top_left(bytes: &[u8]) -> usize {
-    compute_energized_tile_count(bytes, &(0, 0, RIGHT))
+pub fn compute_energized_tile_count_from_top_left(grid: &Grid<u8>) -> usize {
+    compute_energized_tile_count(grid, &((0, 0), Right))
 }
 
-fn compute_max_energized_tile_count_from_any_start(bytes: &[u8]) -> usize {
-    let size = size(bytes);
-    let row_count = size.0 as i32;
-    let col_count = size.1 as i32;
+pub fn compute_max_energized_tile_count_from_any_start(grid: &Grid<u8>) -> usize {
+    let row_count = grid.row_count();
+    let col_count = grid.col_count();
 
     let from_left = (0..row_count)
-        .map(|ri| compute_energized_tile_count(bytes, &(ri, 0, RIGHT)))
+        .map(|ri| compute_energized_tile_count(grid, &((ri, 0), Right)))
         .max()
         .unwrap();
     let from_right = (0..row_count)
-        .map(|ri| compute_energized_tile_count(bytes, &(ri, col_count - 1, LEFT)))
+        .map(|ri| compute_energized_tile_count(grid, &((ri, col_count - 1), Left)))
         .max()
         .unwrap();
     let from_top = (0..col_count)
-        .map(|ci| compute_energized_tile_count(bytes, &(0, ci, DOWN)))
+        .map(|ci| compute_energized_tile_count(grid, &((0, ci), Down)))
         .max()
         .unwrap();
     let from_bottom = (0..col_count)
-        .map(|ci| compute_energized_tile_count(bytes, &(row_count - 1, ci, UP)))
+        .map(|ci| compute_energized_tile_count(grid, &((row_count - 1, ci), Up)))
         .max()
         .unwrap();
 
     *[from_left, from_right, from_top, from_bottom].iter().max().unwrap()
 }
 
-fn compute_energized_tile_count(bytes: &[u8], first_beam: &Beam) -> usize {
-    let (row_count, col_count) = size(bytes);
+fn compute_energized_tile_count(grid: &Grid<u8>, first_beam: &Beam) -> usize {
     let mut beams: Vec<Beam> = vec![*first_beam];
-    let mut energized: HashSet<(usize, usize)> = HashSet::new();
+    let mut energized: HashSet<Coord> = HashSet::new();
 
     // To detect cycles, it is not enough to just check whether an already visited tile is visited
     // again. The direction matters too, e.g. when visiting a '/' from the left, that was already
@@ -79,12 +71,12 @@ fn compute_energized_tile_count(bytes: &[u8], first_beam: &Beam) -> usize {
     while !beams.is_empty() {
         let mut next_beams = vec![];
         for beam in beams {
-            let (new_beams, new_energized) = get_next_beams(&beam, bytes, row_count, col_count);
+            let (new_beams, new_energized) = get_next_beams(&beam, grid);
             energized.extend(new_energized);
             for b in new_beams {
                 if !visited.contains(&b) {
-                    visited.insert(b.clone());
-                    next_beams.push(b.clone());
+                    visited.insert(b);
+                    next_beams.push(b);
                 }
             }
         }
@@ -98,56 +90,29 @@ fn compute_energized_tile_count(bytes: &[u8], first_beam: &Beam) -> usize {
 // Returns a set of energized tiles on the path of the beam, until hitting bounds or a tile that
 // causes direction change. In the latter case, it returns a vector of new beams resulting from that change.
 // TODO: Could use some kind of caching here, but didn't find time for that yet.
-fn get_next_beams(
-    beam: &Beam,
-    bytes: &[u8],
-    row_count: usize,
-    col_count: usize,
-) -> (Vec<Beam>, HashSet<(usize, usize)>) {
-    let &(mut r, mut c, d) = beam;
-    let mut energized: HashSet<(usize, usize)> = HashSet::new();
-    energized.insert((r as usize, c as usize));
-
-    while r >= 0 && c >= 0 && r < row_count as i32 && c < col_count as i32 {
-        match d {
-            UP => r -= 1,
-            RIGHT => c += 1,
-            DOWN => r += 1,
-            LEFT => c -= 1,
-        }
-        if r < 0 || c < 0 || r >= row_count as i32 || c >= col_count as i32 {
-            return (vec![], energized);
-        }
-        energized.insert((r as usize, c as usize));
-        let ch = bytes[r as usize * (col_count + 1) + c as usize];
-        match ch {
+fn get_next_beams(beam: &Beam, grid: &Grid<u8>) -> (Vec<Beam>, HashSet<Coord>) {
+    let &(mut pos, d) = beam;
+    let mut energized: HashSet<Coord> = HashSet::new();
+    energized.insert(pos);
+
+    while let Some(next) = grid.step(pos, d) {
+        pos = next;
+        energized.insert(pos);
+        match grid[pos] {
+            // '/' turns rightward beams up and downward beams left, '\' the other way around
             b'/' => {
-                let dir = match d {
-                    RIGHT => UP,
-                    LEFT => DOWN,
-                    DOWN => LEFT,
-                    UP => RIGHT,
-                };
-                return (vec![(r, c, dir)], energized);
+                let dir = if d.is_horizontal() { d.turn_left() } else { d.turn_right() };
+                return (vec![(pos, dir)], energized);
             }
             b'\\' => {
-                let dir = match d {
-                    RIGHT => DOWN,
-                    LEFT => UP,
-                    DOWN => RIGHT,
-                    UP => LEFT,
-                };
-                return (vec![(r, c, dir)], energized);
+                let dir = if d.is_horizontal() { d.turn_right() } else { d.turn_left() };
+                return (vec![(pos, dir)], energized);
             }
-            b'|' => {
-                if d == LEFT || d == RIGHT {
-                    return (vec![(r, c, UP), (r, c, DOWN)], energized);
-                }
+            b'|' if d.is_horizontal() => {
+                return (vec![(pos, Up), (pos, Down)], energized);
             }
-            b'-' => {
-                if d == DOWN || d == UP {
-                    return (vec![(r, c, LEFT), (r, c, RIGHT)], energized);
-                }
+            b'-' if !d.is_horizontal() => {
+                return (vec![(pos, Left), (pos, Right)], energized);
             }
             _ => (),
         }
@@ -156,23 +121,26 @@ fn get_next_beams(
     (vec![], energized)
 }
 
-fn size(bytes: &[u8]) -> (usize, usize) {
-    let col_count = bytes.iter().position(|&b| b == b'\n').unwrap();
-    let row_count = bytes.len() / col_count;
-    (row_count, col_count)
+pub fn parse_input(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(input, input, |c| {
+        ['.', '/', '\\', '|', '-'].contains(&c).then_some(c as u8)
+    })
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::compute_energized_tile_count_from_top_left(include_bytes!("example"));
+        let res =
+            super::compute_energized_tile_count_from_top_left(&super::parse_input(include_str!("example")).unwrap());
         assert_eq!(46, res);
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::compute_max_energized_tile_count_from_any_start(include_bytes!("example"));
+        let res = super::compute_max_energized_tile_count_from_any_start(
+            &super::parse_input(include_str!("example")).unwrap(),
+        );
         assert_eq!(51, res);
     }
 }
@@ -1,170 +1,117 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashSet};
-
-use itertools::Itertools;
-
-use crate::d17::Direction::{DOWN, LEFT, RIGHT, UP};
-use crate::utils::{get_lines, part_end, part_start};
-
-type Grid = Vec<Vec<u8>>;
-
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
-enum Direction {
-    UP,
-    RIGHT,
-    DOWN,
-    LEFT,
-}
-impl Direction {
-    fn is_opposite(&self, dir: &Direction) -> bool {
-        (*self == UP && *dir == DOWN)
-            || (*self == DOWN && *dir == UP)
-            || (*self == LEFT && *dir == RIGHT)
-            || (*self == RIGHT && *dir == LEFT)
-    }
-}
-
-#[derive(Eq, PartialEq)]
-struct Pos {
-    cum_heat_loss: u64,
-    ri: usize,
-    ci: usize,
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::Direction::Down;
+use crate::utils::{a_star, Coord, Direction, Grid, SearchProblem};
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Crucible {
+    pos: Coord,
     direction: Direction,
     dir_count: usize,
 }
-impl Pos {
-    fn step(&self, grid: &Grid, dir: Direction, max_same_dir_moves: usize) -> Option<Self> {
-        if self.direction.is_opposite(&dir) {
-            return None;
-        }
-        if (self.ri == 0 && dir == UP) || (self.ci == 0 && dir == LEFT) {
-            return None;
-        }
-
-        let next_ri = match dir {
-            UP => self.ri - 1,
-            DOWN => self.ri + 1,
-            _ => self.ri,
-        };
-        let next_ci = match dir {
-            LEFT => self.ci - 1,
-            RIGHT => self.ci + 1,
-            _ => self.ci,
-        };
-        let next_dir_count = if self.direction == dir { self.dir_count + 1 } else { 1 };
-        let mut result = Pos {
-            cum_heat_loss: self.cum_heat_loss,
-            ri: next_ri,
-            ci: next_ci,
-            direction: dir,
-            dir_count: next_dir_count,
-        };
-
-        if result.valid(grid, max_same_dir_moves) {
-            result.cum_heat_loss += *grid.get(next_ri).unwrap().get(next_ci).unwrap() as u64;
-            Some(result)
-        } else {
-            None
-        }
-    }
 
-    fn done(&self, grid: &Grid, min_same_dir_moves: usize) -> bool {
-        self.dir_count >= min_same_dir_moves
-            && self.ri == grid.len() - 1
-            && self.ci == grid.get(self.ri).unwrap().len() - 1
-    }
+// Moving the crucible from the top left to the bottom right, turning or stopping only after `min_same_dir_moves`
+// and moving at most `max_same_dir_moves` in the same direction
+struct City<'a> {
+    grid: &'a Grid<u8>,
+    min_same_dir_moves: usize,
+    max_same_dir_moves: usize,
+    min_block_heat_loss: u64,
+}
 
-    fn valid(&self, grid: &Grid, max_same_dir_moves: usize) -> bool {
-        self.dir_count <= max_same_dir_moves && self.ri < grid.len() && self.ci < grid.get(self.ri).unwrap().len()
+impl SearchProblem for City<'_> {
+    type State = Crucible;
+
+    fn neighbours(&self, crucible: &Crucible) -> Vec<(Crucible, u64)> {
+        // Until having moved `min_same_dir_moves` blocks, the crucible cannot turn
+        let must_go_on = crucible.dir_count > 0 && crucible.dir_count < self.min_same_dir_moves;
+        Direction::ALL
+            .into_iter()
+            .filter(|&dir| !crucible.direction.is_opposite(dir) && (!must_go_on || dir == crucible.direction))
+            .filter_map(|dir| {
+                let dir_count = if crucible.direction == dir { crucible.dir_count + 1 } else { 1 };
+                if dir_count > self.max_same_dir_moves {
+                    return None;
+                }
+                let pos = self.grid.step(crucible.pos, dir)?;
+                let next = Crucible {
+                    pos,
+                    direction: dir,
+                    dir_count,
+                };
+                Some((next, self.grid[pos] as u64))
+            })
+            .collect()
     }
-}
-impl Ord for Pos {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // Note that order of `other` and `self` is flipped
-        // This turns the max-heap into a min-heap
-        other.cum_heat_loss.cmp(&self.cum_heat_loss)
+
+    fn is_goal(&self, crucible: &Crucible) -> bool {
+        crucible.dir_count >= self.min_same_dir_moves
+            && crucible.pos == (self.grid.row_count() - 1, self.grid.col_count() - 1)
     }
-}
-impl PartialOrd for Pos {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+
+    // Each block on the way to the bottom right loses at least as much heat as the coolest block
+    fn heuristic(&self, crucible: &Crucible) -> u64 {
+        let distance = (self.grid.row_count() - 1 - crucible.pos.0) + (self.grid.col_count() - 1 - crucible.pos.1);
+        distance as u64 * self.min_block_heat_loss
     }
 }
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day17;
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", compute_min_heat_loss("d17/input", 0, 3));
-    part_end(start);
-}
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Clumsy Crucible";
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", compute_min_heat_loss("d17/input", 4, 10));
-    part_end(start);
-}
+    type Input = Grid<u8>;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-fn compute_min_heat_loss(file_path: &str, min_same_dir_moves: usize, max_same_dir_moves: usize) -> u64 {
-    let grid = parse_input(file_path);
-    let mut heap = BinaryHeap::new();
-    let mut seen = HashSet::new();
-    heap.push(Pos {
-        cum_heat_loss: 0,
-        ri: 0,
-        ci: 0,
-        direction: DOWN,
-        dir_count: 0,
-    });
-
-    while let Some(cur_min) = heap.pop() {
-        let dir_options = if cur_min.dir_count > 0 && cur_min.dir_count < min_same_dir_moves {
-            vec![cur_min.direction.clone()]
-        } else {
-            vec![RIGHT, DOWN, LEFT, UP]
-        };
-
-        for dir in dir_options {
-            if let Some(next) = cur_min.step(&grid, dir, max_same_dir_moves) {
-                let seen_key = (next.ri, next.ci, next.direction.clone(), next.dir_count);
-                if seen.contains(&seen_key) {
-                    continue;
-                }
-                seen.insert(seen_key);
+    fn parse(&self, input: &str) -> Result<Grid<u8>> {
+        parse_input(input)
+    }
 
-                if next.done(&grid, min_same_dir_moves) {
-                    return next.cum_heat_loss;
-                }
+    fn part_one(&self, input: &Grid<u8>) -> Result<u64> {
+        compute_min_heat_loss(input, 0, 3)
+    }
 
-                heap.push(next);
-            }
-        }
+    fn part_two(&self, input: &Grid<u8>) -> Result<u64> {
+        compute_min_heat_loss(input, 4, 10)
     }
+}
+
+pub fn compute_min_heat_loss(grid: &Grid<u8>, min_same_dir_moves: usize, max_same_dir_moves: usize) -> Result<u64> {
+    let city = City {
+        grid,
+        min_same_dir_moves,
+        max_same_dir_moves,
+        min_block_heat_loss: grid.iter().map(|(_, &loss)| loss as u64).min().unwrap_or(0),
+    };
+    let start = Crucible {
+        pos: (0, 0),
+        direction: Down,
+        dir_count: 0,
+    };
 
-    panic!("Could not find solution")
+    a_star(&city, start)
+        .map(|path| path.cost)
+        .ok_or_else(|| Error::new("There is no path to the bottom right"))
 }
 
-fn parse_input(file_path: &str) -> Grid {
-    get_lines(file_path)
-        .iter()
-        .map(|row| row.chars().map(|c| c.to_digit(10).unwrap() as u8).collect_vec())
-        .collect_vec()
+pub fn parse_input(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(input, input, |c| c.to_digit(10).map(|d| d as u8))
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn example_part_one() {
-        let res = super::compute_min_heat_loss("d17/example", 0, 3);
-        assert_eq!(102, res);
+        let res = super::compute_min_heat_loss(&super::parse_input(include_str!("example")).unwrap(), 0, 3);
+        assert_eq!(102, res.unwrap());
     }
 
     #[test]
     fn example_part_two() {
-        let res = super::compute_min_heat_loss("d17/example", 4, 10);
-        assert_eq!(94, res);
+        let res = super::compute_min_heat_loss(&super::parse_input(include_str!("example")).unwrap(), 4, 10);
+        assert_eq!(94, res.unwrap());
     }
 }
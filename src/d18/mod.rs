@@ -1,101 +1,66 @@
-use std::str::FromStr;
-
+use itertools::Itertools;
 use num::abs;
 
-use crate::d18::Direction::{EAST, NORTH, SOUTH, WEST};
-use crate::utils::{get_lines, part_end, part_start};
-
-enum Direction {
-    NORTH,
-    EAST,
-    SOUTH,
-    WEST,
-}
-
-impl FromStr for Direction {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "U" => Ok(NORTH),
-            "R" => Ok(EAST),
-            "D" => Ok(SOUTH),
-            "L" => Ok(WEST),
-            _ => Err(format!("Invalid direction string {s}")),
-        }
-    }
-}
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{parse_at, Direction};
 
-impl From<u8> for Direction {
-    fn from(v: u8) -> Self {
-        match v {
-            0 => EAST,
-            1 => SOUTH,
-            2 => WEST,
-            3 => NORTH,
-            _ => panic!("Invalid direction index {v}"),
-        }
-    }
-}
+// Steps as given and steps as decoded from the hex colors
+pub type DigPlan = (Vec<Step>, Vec<Step>);
 
-struct Step {
+pub struct Step {
     direction: Direction,
     count: u64,
 }
 
 impl Step {
-    fn v_steps(&self) -> i64 {
-        match self.direction {
-            NORTH => -1 * self.count as i64,
-            SOUTH => 1 * self.count as i64,
-            _ => 0,
-        }
-    }
-    fn h_steps(&self) -> i64 {
-        match self.direction {
-            EAST => 1 * self.count as i64,
-            WEST => -1 * self.count as i64,
-            _ => 0,
-        }
+    // Change of row and column when digging the whole step
+    fn offset(&self) -> (i64, i64) {
+        let (dr, dc) = self.direction.delta();
+        (dr * self.count as i64, dc * self.count as i64)
     }
 }
 
-pub fn main() {
-    part_one();
-    part_two();
-}
+pub struct Day18;
 
-fn part_one() {
-    let start = part_start(1);
-    println!("Result: {}", calc_cubic_meters("d18/input", false));
-    part_end(start);
-}
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Lavaduct Lagoon";
+
+    type Input = DigPlan;
+    type PartOne = u64;
+    type PartTwo = u64;
 
-fn part_two() {
-    let start = part_start(2);
-    println!("Result: {}", calc_cubic_meters("d18/input", true));
-    part_end(start);
+    fn parse(&self, input: &str) -> Result<DigPlan> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &DigPlan) -> Result<u64> {
+        Ok(calc_cubic_meters(&input.0))
+    }
+
+    fn part_two(&self, input: &DigPlan) -> Result<u64> {
+        Ok(calc_cubic_meters(&input.1))
+    }
 }
 
-fn calc_cubic_meters(file_path: &str, use_hex: bool) -> u64 {
-    let steps = parse_input(file_path, use_hex);
+pub fn calc_cubic_meters(steps: &[Step]) -> u64 {
     let mut cur = (0i64, 0i64);
     let mut edges = vec![];
 
     let mut outer_points = 0;
     for step in steps {
         outer_points += step.count;
-        cur = (cur.0 + step.v_steps(), cur.1 + step.h_steps());
+        let (dr, dc) = step.offset();
+        cur = (cur.0 + dr, cur.1 + dc);
         edges.push(cur);
     }
 
     // Shoelace formula: https://en.wikipedia.org/wiki/Shoelace_formula
     let sum1 = (0..(edges.len() - 1))
-        .into_iter()
         .map(|i| edges.get(i).unwrap().1 * edges.get(i + 1).unwrap().0)
         .sum::<i64>();
     let sum2 = (1..edges.len())
-        .into_iter()
         .map(|i| edges.get(i).unwrap().1 * edges.get(i - 1).unwrap().0)
         .sum::<i64>();
     let inner_area = (0.5 * abs(sum1 - sum2) as f64).round() as u64;
@@ -106,45 +71,52 @@ fn calc_cubic_meters(file_path: &str, use_hex: bool) -> u64 {
     inner_area + (0.5 * outer_points as f64).round() as u64 + 1
 }
 
-fn parse_input(file_path: &str, use_hex: bool) -> Vec<Step> {
-    return get_lines(file_path)
-        .iter()
-        .map(|line| line.split_whitespace())
-        .map(|mut parts| {
-            let dir = parts.next().unwrap();
-            let count = parts.next().unwrap();
-            let hex = parts.next().unwrap();
-
-            if use_hex {
-                let dir = hex.chars().nth(7).unwrap().to_digit(10).unwrap() as u8;
-                let count = u64::from_str_radix(&hex[2..7], 16).unwrap();
-                Step {
-                    direction: dir.into(),
-                    count,
-                }
-            } else {
-                Step {
-                    direction: dir.parse().unwrap(),
-                    count: count.parse().unwrap(),
-                }
-            }
+pub fn parse_input(input: &str) -> Result<DigPlan> {
+    let steps = input
+        .lines()
+        .map(|line| {
+            let [dir, count, hex] = line.split_whitespace().collect_vec()[..] else {
+                return Err(Error::at(input, line, "Expected direction, count and color"));
+            };
+
+            let step = Step {
+                direction: dir.parse().map_err(|err| Error::at(input, dir, err))?,
+                count: parse_at(input, count)?,
+            };
+            let invalid_color = || Error::at(input, hex, "Expected color like \"(#70c710)\"");
+            let hex = hex
+                .strip_prefix("(#")
+                .and_then(|hex| hex.strip_suffix(')'))
+                .filter(|hex| hex.len() == 6 && hex.is_ascii())
+                .ok_or_else(invalid_color)?;
+            let hex_dir = hex[5..].parse::<u8>().map_err(|_| invalid_color())?;
+            let hex_step = Step {
+                direction: Direction::from_index(hex_dir).ok_or_else(invalid_color)?,
+                count: u64::from_str_radix(&hex[..5], 16).map_err(|_| invalid_color())?,
+            };
+            Ok((step, hex_step))
         })
-        .collect();
+        .collect::<Result<Vec<_>>>()?;
+    if steps.is_empty() {
+        return Err(Error::new("Empty dig plan"));
+    }
+
+    Ok(steps.into_iter().unzip())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::d18::calc_cubic_meters;
+    use crate::d18::{calc_cubic_meters, parse_input};
 
     #[test]
     fn example_part_one() {
-        let result = calc_cubic_meters("d18/example", false);
+        let result = calc_cubic_meters(&parse_input(include_str!("example")).unwrap().0);
         assert_eq!(62, result);
     }
 
     #[test]
     fn example_part_two() {
-        let result = calc_cubic_meters("d18/example", true);
+        let result = calc_cubic_meters(&parse_input(include_str!("example")).unwrap().1);
         assert_eq!(952408144115, result);
     }
 }
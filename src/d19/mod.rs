@@ -0,0 +1,334 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{blocks, each_line, parse_at, separated, split_once_at};
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+const MIN_RATING: u64 = 1;
+const MAX_RATING: u64 = 4000;
+
+// Ratings of a part, in the order of CATEGORIES
+pub type Part = [u64; 4];
+// Inclusive range of ratings
+type Range = (u64, u64);
+// Range of ratings per category
+type Ranges = [Range; 4];
+
+pub enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+// Condition like "a<2006", a rule without one always applies
+pub struct Condition {
+    category: usize,
+    less_than: bool,
+    value: u64,
+}
+
+pub struct Rule {
+    condition: Option<Condition>,
+    target: Target,
+}
+
+// Workflows by name and the parts to sort
+pub type System = (HashMap<String, Vec<Rule>>, Vec<Part>);
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Aplenty";
+
+    type Input = System;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(&self, input: &str) -> Result<System> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &System) -> Result<u64> {
+        sum_accepted_ratings(input)
+    }
+
+    fn part_two(&self, input: &System) -> Result<u64> {
+        count_accepted_combinations(&input.0)
+    }
+}
+
+pub fn sum_accepted_ratings((workflows, parts): &System) -> Result<u64> {
+    let mut sum = 0;
+    for part in parts {
+        if is_accepted(workflows, part)? {
+            sum += part.iter().sum::<u64>();
+        }
+    }
+
+    Ok(sum)
+}
+
+fn is_accepted(workflows: &HashMap<String, Vec<Rule>>, part: &Part) -> Result<bool> {
+    let mut name = "in";
+    // Without a loop, every workflow is visited at most once
+    for _ in 0..workflows.len() {
+        let rule = workflows[name]
+            .iter()
+            .find(|rule| rule.condition.as_ref().is_none_or(|c| c.matches(part[c.category])))
+            .unwrap();
+        match &rule.target {
+            Target::Accept => return Ok(true),
+            Target::Reject => return Ok(false),
+            Target::Workflow(next) => name = next,
+        }
+    }
+
+    Err(Error::new(format!("The workflows loop, e.g. via {name}")))
+}
+
+pub fn count_accepted_combinations(workflows: &HashMap<String, Vec<Rule>>) -> Result<u64> {
+    count_accepted(workflows, "in", [(MIN_RATING, MAX_RATING); 4], 0)
+}
+
+// Counts the accepted combinations within the ranges, by splitting them at each condition on the way
+fn count_accepted(workflows: &HashMap<String, Vec<Rule>>, name: &str, mut ranges: Ranges, depth: usize) -> Result<u64> {
+    if depth > workflows.len() {
+        return Err(Error::new(format!("The workflows loop, e.g. via {name}")));
+    }
+
+    let mut count = 0;
+    for rule in &workflows[name] {
+        let Some(condition) = &rule.condition else {
+            return Ok(count + count_target(workflows, &rule.target, ranges, depth)?);
+        };
+        let (matching, rest) = condition.split(ranges[condition.category]);
+        if let Some(matching) = matching {
+            let mut matching_ranges = ranges;
+            matching_ranges[condition.category] = matching;
+            count += count_target(workflows, &rule.target, matching_ranges, depth)?;
+        }
+        // The remaining rules only see the ratings not matching the condition
+        match rest {
+            Some(rest) => ranges[condition.category] = rest,
+            None => break,
+        }
+    }
+
+    Ok(count)
+}
+
+fn count_target(workflows: &HashMap<String, Vec<Rule>>, target: &Target, ranges: Ranges, depth: usize) -> Result<u64> {
+    Ok(match target {
+        Target::Accept => ranges.iter().map(|(from, to)| to - from + 1).product(),
+        Target::Reject => 0,
+        Target::Workflow(next) => count_accepted(workflows, next, ranges, depth + 1)?,
+    })
+}
+
+impl Condition {
+    fn matches(&self, rating: u64) -> bool {
+        if self.less_than {
+            rating < self.value
+        } else {
+            rating > self.value
+        }
+    }
+
+    // Splits the range into the ratings matching the condition and the rest, both might be empty
+    fn split(&self, (from, to): Range) -> (Option<Range>, Option<Range>) {
+        let (matching, rest) = if self.less_than {
+            ((from, to.min(self.value - 1)), (from.max(self.value), to))
+        } else {
+            ((from.max(self.value + 1), to), (from, to.min(self.value)))
+        };
+        let non_empty = |(from, to): Range| (from <= to).then_some((from, to));
+        (non_empty(matching), non_empty(rest))
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<System> {
+    let [workflow_block, part_block] = blocks(input).collect::<Vec<_>>()[..] else {
+        return Err(Error::new("Expected workflows and parts, separated by a blank line"));
+    };
+
+    let workflows = each_line(workflow_block, |line| parse_workflow(input, line))?;
+    let mut names = HashSet::new();
+    for &(name, _) in &workflows {
+        if !names.insert(name) {
+            return Err(Error::at(input, name, format!("Workflow {name} is defined twice")));
+        }
+    }
+    if !names.contains("in") {
+        return Err(Error::new("There is no workflow \"in\""));
+    }
+    let unknown = workflows
+        .iter()
+        .flat_map(|(_, rules)| rules)
+        .find(|(rule, _)| matches!(&rule.target, Target::Workflow(next) if !names.contains(next.as_str())));
+    if let Some((_, target)) = unknown {
+        return Err(Error::at(input, target, format!("Unknown workflow {target:?}")));
+    }
+    let workflows = workflows
+        .into_iter()
+        .map(|(name, rules)| (name.to_string(), rules.into_iter().map(|(rule, _)| rule).collect()))
+        .collect();
+
+    let parts = each_line(part_block, |line| parse_part(input, line))?;
+    Ok((workflows, parts))
+}
+
+// Workflow like "px{a<2006:qkq,m>2090:A,rfg}", each rule together with its target in the input
+fn parse_workflow<'a>(input: &str, line: &'a str) -> Result<(&'a str, Vec<(Rule, &'a str)>)> {
+    let (name, rules) = split_once_at(input, line, "{")?;
+    let rules = rules
+        .strip_suffix('}')
+        .ok_or_else(|| Error::at(input, line, "Missing '}'"))?;
+    let rules = separated(rules, ",", |rule| parse_rule(input, rule))?;
+    if rules.last().is_none_or(|(rule, _)| rule.condition.is_some()) {
+        return Err(Error::at(input, line, "The last rule must not have a condition"));
+    }
+
+    Ok((name, rules))
+}
+
+// Rule like "a<2006:qkq" or "A"
+fn parse_rule<'a>(input: &str, rule: &'a str) -> Result<(Rule, &'a str)> {
+    let (condition, target_name) = match rule.split_once(':') {
+        Some((condition, target)) => (Some(parse_condition(input, condition)?), target),
+        None => (None, rule),
+    };
+    let target = match target_name {
+        "A" => Target::Accept,
+        "R" => Target::Reject,
+        "" => return Err(Error::at(input, rule, "Missing target")),
+        name => Target::Workflow(name.to_string()),
+    };
+
+    Ok((Rule { condition, target }, target_name))
+}
+
+fn parse_condition(input: &str, condition: &str) -> Result<Condition> {
+    let mut chars = condition.chars();
+    let (Some(category), Some(op)) = (chars.next(), chars.next()) else {
+        return Err(Error::at(input, condition, "Expected a condition like \"a<2006\""));
+    };
+    let category = parse_category(input, condition, category)?;
+    let less_than = match op {
+        '<' => true,
+        '>' => false,
+        _ => return Err(Error::at(input, &condition[1..], "Expected '<' or '>'")),
+    };
+    let value = parse_at(input, &condition[2..])?;
+    if !(MIN_RATING..=MAX_RATING).contains(&value) {
+        return Err(Error::at(
+            input,
+            &condition[2..],
+            "Values have to be between 1 and 4000",
+        ));
+    }
+
+    Ok(Condition {
+        category,
+        less_than,
+        value,
+    })
+}
+
+fn parse_category(input: &str, target: &str, category: char) -> Result<usize> {
+    CATEGORIES
+        .iter()
+        .position(|&c| c == category)
+        .ok_or_else(|| Error::at(input, target, format!("Invalid category {category:?}")))
+}
+
+// Part like "{x=787,m=2655,a=1222,s=2876}"
+fn parse_part(input: &str, line: &str) -> Result<Part> {
+    let ratings = line
+        .strip_prefix('{')
+        .and_then(|line| line.strip_suffix('}'))
+        .ok_or_else(|| Error::at(input, line, "Expected a part like \"{x=1,m=2,a=3,s=4}\""))?;
+    let mut part = [None; 4];
+    for (category, rating) in separated(ratings, ",", |rating| split_once_at(input, rating, "="))? {
+        let index = match category.chars().collect::<Vec<_>>()[..] {
+            [c] => parse_category(input, category, c)?,
+            _ => return Err(Error::at(input, category, format!("Invalid category {category:?}"))),
+        };
+        if part[index].is_some() {
+            return Err(Error::at(
+                input,
+                category,
+                format!("Category {category} is rated twice"),
+            ));
+        }
+        part[index] = Some(parse_at(input, rating)?);
+    }
+
+    match part {
+        [Some(x), Some(m), Some(a), Some(s)] => Ok([x, m, a, s]),
+        _ => Err(Error::at(input, line, "Expected ratings for x, m, a and s")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn example_part_one() {
+        let res = super::sum_accepted_ratings(&super::parse_input(include_str!("example")).unwrap());
+        assert_eq!(19114, res.unwrap());
+    }
+
+    #[test]
+    fn example_part_two() {
+        let res = super::count_accepted_combinations(&super::parse_input(include_str!("example")).unwrap().0);
+        assert_eq!(167409079868000, res.unwrap());
+    }
+
+    #[test]
+    fn should_locate_unknown_workflow() {
+        let error = super::parse_input("in{x<10:abc,R}\n\n{x=1,m=2,a=3,s=4}").err().unwrap();
+        assert_eq!("line 1, column 9: Unknown workflow \"abc\"", error.to_string());
+    }
+
+    #[test]
+    fn should_locate_duplicate_workflow() {
+        let error = super::parse_input(
+            "in{A}
+ab{R}
+in{R}
+
+{x=1,m=2,a=3,s=4}",
+        )
+        .err()
+        .unwrap();
+        assert_eq!("line 3, column 1: Workflow in is defined twice", error.to_string());
+    }
+
+    #[test]
+    fn should_locate_invalid_ratings() {
+        let error = super::parse_input(
+            "in{A}
+
+{x=1,mm=2,a=3,s=4}",
+        )
+        .err()
+        .unwrap();
+        assert_eq!("line 3, column 6: Invalid category \"mm\"", error.to_string());
+        let error = super::parse_input(
+            "in{A}
+
+{x=1,m=2,a=3,x=4}",
+        )
+        .err()
+        .unwrap();
+        assert_eq!("line 3, column 14: Category x is rated twice", error.to_string());
+    }
+
+    #[test]
+    fn should_detect_loop() {
+        let system = super::parse_input("in{x<10:ab,R}\nab{in}\n\n{x=1,m=2,a=3,s=4}").unwrap();
+        assert!(super::sum_accepted_ratings(&system).is_err());
+        assert!(super::count_accepted_combinations(&system.0).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::{HashMap, VecDeque};
+
+use itertools::Itertools;
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{each_line, lcm_all, separated, split_once_at};
+
+// Presses after which it is given up to find a cycle of a module feeding `rx`
+const MAX_PRESSES: usize = 1_000_000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    // Modules that only receive pulses, like "output" or "rx"
+    Sink,
+}
+
+pub struct Module {
+    name: String,
+    kind: Kind,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
+
+// All modules, connected by their index, and the index of the broadcaster
+pub type Network = (Vec<Module>, usize);
+
+// Pulse as (sender, receiver, high)
+type Pulse = (usize, usize, bool);
+
+// Whether each flip-flop is on, and the last pulse each conjunction remembers per input, in order of its inputs
+struct State {
+    on: Vec<bool>,
+    memory: Vec<Vec<bool>>,
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Pulse Propagation";
+
+    type Input = Network;
+    type PartOne = usize;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Network> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Network) -> Result<usize> {
+        Ok(count_pulse_product(input, 1000))
+    }
+
+    fn part_two(&self, input: &Network) -> Result<i64> {
+        count_presses_until_rx_low(input)
+    }
+}
+
+pub fn count_pulse_product(network: &Network, presses: usize) -> usize {
+    let mut state = State::new(&network.0);
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..presses {
+        press_button(network, &mut state, |(_, _, is_high)| {
+            if is_high {
+                high += 1;
+            } else {
+                low += 1;
+            }
+        });
+    }
+
+    low * high
+}
+
+// Simulating until `rx` receives a low pulse takes way too long. In the puzzle input, `rx` is fed by a single
+// conjunction, which sends a low pulse once all of its inputs sent a high pulse in the same press. Each of them
+// does so in a cycle starting at press zero, so like for the ghosts in d08, the answer is the LCM of the cycles.
+pub fn count_presses_until_rx_low(network: &Network) -> Result<i64> {
+    let (modules, _) = network;
+    let rx = modules
+        .iter()
+        .position(|m| m.name == "rx")
+        .ok_or_else(|| Error::new("There is no module rx"))?;
+    let hub = match modules[rx].inputs[..] {
+        [hub] if modules[hub].kind == Kind::Conjunction => hub,
+        _ => return Err(Error::new("rx has to be fed by a single conjunction")),
+    };
+
+    let mut cycles: HashMap<usize, i64> = HashMap::new();
+    let mut state = State::new(modules);
+    for presses in 1..=MAX_PRESSES {
+        press_button(network, &mut state, |(from, to, is_high)| {
+            if to == hub && is_high {
+                cycles.entry(from).or_insert(presses as i64);
+            }
+        });
+        if cycles.len() == modules[hub].inputs.len() {
+            return Ok(lcm_all(cycles.into_values()));
+        }
+    }
+
+    Err(Error::new(format!(
+        "Not all inputs of {} sent a high pulse within {MAX_PRESSES} presses",
+        modules[hub].name
+    )))
+}
+
+// Sends a low pulse to the broadcaster and processes all resulting pulses in the order they were sent
+fn press_button(network: &Network, state: &mut State, mut on_pulse: impl FnMut(Pulse)) {
+    let (modules, broadcaster) = network;
+    let mut queue = VecDeque::from([(*broadcaster, *broadcaster, false)]);
+    while let Some(pulse @ (from, to, is_high)) = queue.pop_front() {
+        on_pulse(pulse);
+        let module = &modules[to];
+        let output = match module.kind {
+            Kind::Broadcaster => Some(is_high),
+            Kind::FlipFlop if !is_high => {
+                state.on[to] = !state.on[to];
+                Some(state.on[to])
+            }
+            Kind::FlipFlop => None,
+            Kind::Conjunction => {
+                let input = module.inputs.iter().position(|&i| i == from).unwrap();
+                state.memory[to][input] = is_high;
+                Some(!state.memory[to].iter().all(|&high| high))
+            }
+            Kind::Sink => None,
+        };
+        if let Some(output) = output {
+            queue.extend(module.outputs.iter().map(|&next| (to, next, output)));
+        }
+    }
+}
+
+impl State {
+    fn new(modules: &[Module]) -> State {
+        State {
+            on: vec![false; modules.len()],
+            memory: modules.iter().map(|m| vec![false; m.inputs.len()]).collect(),
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Network> {
+    let definitions = each_line(input, |line| {
+        let (module, outputs) = split_once_at(input, line, " -> ")?;
+        let (kind, name) = match module.chars().next() {
+            Some('%') => (Kind::FlipFlop, &module[1..]),
+            Some('&') => (Kind::Conjunction, &module[1..]),
+            _ if module == "broadcaster" => (Kind::Broadcaster, module),
+            _ => {
+                return Err(Error::at(
+                    input,
+                    module,
+                    "Expected \"broadcaster\", \"%<name>\" or \"&<name>\"",
+                ))
+            }
+        };
+        Ok((name, kind, separated(outputs, ",", Ok)?))
+    })?;
+
+    // Modules are indexed in order of their definition, followed by the sinks in order of their first use
+    let mut modules = Vec::new();
+    let mut indices = HashMap::new();
+    for &(name, kind, _) in &definitions {
+        if indices.insert(name, modules.len()).is_some() {
+            return Err(Error::at(input, name, format!("Module {name} is defined twice")));
+        }
+        modules.push(Module::new(name, kind));
+    }
+    for (name, _, outputs) in &definitions {
+        let from = indices[name];
+        for output in outputs {
+            let to = *indices.entry(output).or_insert_with(|| {
+                modules.push(Module::new(output, Kind::Sink));
+                modules.len() - 1
+            });
+            modules[from].outputs.push(to);
+            modules[to].inputs.push(from);
+        }
+    }
+
+    let broadcaster = modules
+        .iter()
+        .position(|m| m.kind == Kind::Broadcaster)
+        .ok_or_else(|| Error::new("There is no broadcaster"))?;
+    if modules.iter().any(|m| m.inputs.iter().duplicates().next().is_some()) {
+        return Err(Error::new("Modules can only be connected once"));
+    }
+    Ok((modules, broadcaster))
+}
+
+impl Module {
+    fn new(name: &str, kind: Kind) -> Module {
+        Module {
+            name: name.to_string(),
+            kind,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn example_part_one_a() {
+        let res = super::count_pulse_product(&super::parse_input(include_str!("example_part1a")).unwrap(), 1000);
+        assert_eq!(32000000, res);
+    }
+
+    #[test]
+    fn example_part_one_b() {
+        let res = super::count_pulse_product(&super::parse_input(include_str!("example_part1b")).unwrap(), 1000);
+        assert_eq!(11687500, res);
+    }
+
+    #[test]
+    fn should_require_rx() {
+        let res = super::count_presses_until_rx_low(&super::parse_input(include_str!("example_part1b")).unwrap());
+        assert_eq!("There is no module rx", res.unwrap_err().to_string());
+    }
+
+    #[test]
+    fn should_find_first_low_pulse_to_rx() {
+        // The inverters send a high pulse every 2nd and every 4th press
+        let network = "broadcaster -> a, b\n%a -> ia\n&ia -> hub\n%b -> c\n%c -> ib\n&ib -> hub\n&hub -> rx";
+        let res = super::count_presses_until_rx_low(&super::parse_input(network).unwrap());
+        assert_eq!(4, res.unwrap());
+    }
+}
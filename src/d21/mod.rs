@@ -0,0 +1,151 @@
+use std::collections::{HashMap, VecDeque};
+
+use itertools::Itertools;
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{extrapolate, Coord, Direction, Grid};
+
+const STEPS: usize = 64;
+const TILED_STEPS: usize = 26501365;
+
+// Start position and whether each tile is a garden plot (rather than a rock)
+pub type Garden = (Coord, Grid<bool>);
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Step Counter";
+
+    type Input = Garden;
+    type PartOne = usize;
+    type PartTwo = i64;
+
+    fn parse(&self, input: &str) -> Result<Garden> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Garden) -> Result<usize> {
+        Ok(count_reachable_plots(input, STEPS, false))
+    }
+
+    fn part_two(&self, input: &Garden) -> Result<i64> {
+        extrapolate_reachable_plots(input, TILED_STEPS)
+    }
+}
+
+pub fn count_reachable_plots(garden: &Garden, steps: usize, tiled: bool) -> usize {
+    count_reachable_plots_for_each(garden, &[steps], tiled)[0]
+}
+
+// Walking the tiled garden for millions of steps is impossible. In the puzzle input, the row and column of the start
+// are free of rocks, so the reachable area grows by one copy of the garden in every direction each `size` steps.
+// The count is then a quadratic polynomial in the number of copies, which is sampled thrice and extrapolated.
+pub fn extrapolate_reachable_plots(garden: &Garden, steps: usize) -> Result<i64> {
+    let ((ri, ci), grid) = garden;
+    let size = grid.row_count();
+    if grid.col_count() != size || *ri != size / 2 || *ci != size / 2 {
+        return Err(Error::new("The garden has to be square, with the start in its center"));
+    }
+
+    let rest = steps % size;
+    if steps < rest + 2 * size {
+        return Ok(count_reachable_plots(garden, steps, true) as i64);
+    }
+    let samples = [rest, rest + size, rest + 2 * size];
+    let counts = count_reachable_plots_for_each(garden, &samples, true)
+        .into_iter()
+        .map(|count| count as i64)
+        .collect_vec();
+    Ok(extrapolate(&counts, (steps / size) as i64))
+}
+
+// Plots on which a walk of each number of steps can end. Walking back and forth, these are all plots reachable in at
+// most that many steps, with the same parity as the number of steps.
+fn count_reachable_plots_for_each(garden: &Garden, steps: &[usize], tiled: bool) -> Vec<usize> {
+    let distances = find_distances(garden, *steps.iter().max().unwrap_or(&0), tiled);
+    steps
+        .iter()
+        .map(|&steps| {
+            distances
+                .values()
+                .filter(|&&distance| distance <= steps && distance % 2 == steps % 2)
+                .count()
+        })
+        .collect()
+}
+
+// Distances of all plots reachable within `max_steps`, by position relative to the start
+fn find_distances(((ri, ci), grid): &Garden, max_steps: usize, tiled: bool) -> HashMap<(i64, i64), usize> {
+    let (row_count, col_count) = (grid.row_count() as i64, grid.col_count() as i64);
+    let is_plot = |(r, c): (i64, i64)| {
+        let (r, c) = (r + *ri as i64, c + *ci as i64);
+        if tiled {
+            grid[(r.rem_euclid(row_count) as usize, c.rem_euclid(col_count) as usize)]
+        } else {
+            (0..row_count).contains(&r) && (0..col_count).contains(&c) && grid[(r as usize, c as usize)]
+        }
+    };
+
+    let mut distances = HashMap::from([((0, 0), 0)]);
+    let mut queue = VecDeque::from([((0, 0), 0)]);
+    while let Some(((r, c), distance)) = queue.pop_front() {
+        if distance == max_steps {
+            continue;
+        }
+        for (dr, dc) in Direction::ALL.map(Direction::delta) {
+            let next = (r + dr, c + dc);
+            if is_plot(next) && !distances.contains_key(&next) {
+                distances.insert(next, distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+pub fn parse_input(input: &str) -> Result<Garden> {
+    let grid = Grid::parse(input, input, |c| ".#S".contains(c).then_some(c))?;
+    let starts = grid
+        .iter()
+        .filter(|&(_, &c)| c == 'S')
+        .map(|(pos, _)| pos)
+        .collect_vec();
+    let &start = starts.first().ok_or_else(|| Error::new("There is no start tile"))?;
+    if let Some(&(ri, ci)) = starts.get(1) {
+        return Err(Error::at_position(ri + 1, ci + 1, "Second start tile"));
+    }
+
+    let plots = Grid::from_rows(grid.rows().map(|row| row.iter().map(|&c| c != '#').collect()).collect());
+    Ok((start, plots))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn example_part_one() {
+        let res = super::count_reachable_plots(&super::parse_input(include_str!("example")).unwrap(), 6, false);
+        assert_eq!(16, res);
+    }
+
+    #[test]
+    fn example_part_two() {
+        let garden = super::parse_input(include_str!("example")).unwrap();
+        assert_eq!(50, super::count_reachable_plots(&garden, 10, true));
+        assert_eq!(1594, super::count_reachable_plots(&garden, 50, true));
+        assert_eq!(6536, super::count_reachable_plots(&garden, 100, true));
+    }
+
+    #[test]
+    fn should_extrapolate_open_garden() {
+        // Without any rocks, the plots reachable in n steps form a diamond of (n + 1)² plots
+        let garden = super::parse_input(".....\n.....\n..S..\n.....\n.....").unwrap();
+        assert_eq!(101 * 101, super::extrapolate_reachable_plots(&garden, 100).unwrap());
+        assert_eq!(
+            super::count_reachable_plots(&garden, 42, true) as i64,
+            super::extrapolate_reachable_plots(&garden, 42).unwrap()
+        );
+    }
+}
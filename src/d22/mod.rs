@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::{each_line, parse_at, separated, split_once_at};
+
+// Cubes at both ends of a brick as [x, y, z], with each coordinate of `from` not greater than in `to`
+pub struct Brick {
+    from: [usize; 3],
+    to: [usize; 3],
+}
+
+/// Which bricks rest on which, once all of them settled. Bricks are indexed in the order of the input.
+pub struct SupportGraph {
+    // Bricks resting directly on each brick
+    supports: Vec<Vec<usize>>,
+    // Bricks each brick rests directly on, empty for bricks on the ground
+    supported_by: Vec<Vec<usize>>,
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Sand Slabs";
+
+    type Input = SupportGraph;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    // Both parts need the settled bricks, so they are settled only once
+    fn parse(&self, input: &str) -> Result<SupportGraph> {
+        Ok(settle(&parse_input(input)?))
+    }
+
+    fn part_one(&self, input: &SupportGraph) -> Result<usize> {
+        Ok(count_safely_removable(input))
+    }
+
+    fn part_two(&self, input: &SupportGraph) -> Result<usize> {
+        Ok(count_chain_reaction_falls(input))
+    }
+}
+
+pub fn count_safely_removable(graph: &SupportGraph) -> usize {
+    (0..graph.len())
+        .filter(|&brick| graph.is_safely_removable(brick))
+        .count()
+}
+
+pub fn count_chain_reaction_falls(graph: &SupportGraph) -> usize {
+    (0..graph.len())
+        .map(|brick| graph.falling_if_removed(brick).len())
+        .sum()
+}
+
+/// Lets all bricks fall as far as possible, lowest first, and records which bricks they come to rest on
+pub fn settle(bricks: &[Brick]) -> SupportGraph {
+    let mut supports = vec![Vec::new(); bricks.len()];
+    let mut supported_by = vec![Vec::new(); bricks.len()];
+    // Height of the topmost cube and the brick it belongs to, for each (x, y) that is covered already
+    let mut tops: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    for i in (0..bricks.len()).sorted_by_key(|&i| bricks[i].from[2]) {
+        let brick = &bricks[i];
+        let area = brick.area().collect_vec();
+        let ground = area
+            .iter()
+            .filter_map(|xy| tops.get(xy))
+            .map(|&(z, _)| z)
+            .max()
+            .unwrap_or(0);
+        let below = area
+            .iter()
+            .filter_map(|xy| tops.get(xy))
+            .filter(|&&(z, _)| z == ground)
+            .map(|&(_, below)| below)
+            .unique()
+            .collect_vec();
+        for &below in &below {
+            supports[below].push(i);
+        }
+        supported_by[i] = below;
+
+        let top = ground + brick.to[2] - brick.from[2] + 1;
+        tops.extend(area.into_iter().map(|xy| (xy, (top, i))));
+    }
+
+    SupportGraph { supports, supported_by }
+}
+
+impl SupportGraph {
+    pub fn len(&self) -> usize {
+        self.supports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.supports.is_empty()
+    }
+
+    /// Bricks resting directly on the given brick
+    pub fn supports(&self, brick: usize) -> &[usize] {
+        &self.supports[brick]
+    }
+
+    /// Bricks the given brick rests directly on
+    pub fn supported_by(&self, brick: usize) -> &[usize] {
+        &self.supported_by[brick]
+    }
+
+    /// Whether no other brick falls when the given brick is disintegrated
+    pub fn is_safely_removable(&self, brick: usize) -> bool {
+        self.supports[brick]
+            .iter()
+            .all(|&above| self.supported_by[above].len() > 1)
+    }
+
+    /// All other bricks that fall in a chain reaction when the given brick is disintegrated, in ascending order
+    pub fn falling_if_removed(&self, brick: usize) -> Vec<usize> {
+        let mut fallen = HashSet::from([brick]);
+        let mut queue = VecDeque::from([brick]);
+        while let Some(falling) = queue.pop_front() {
+            for &above in &self.supports[falling] {
+                // A brick falls once the last of the bricks it rests on fell
+                if !fallen.contains(&above) && self.supported_by[above].iter().all(|b| fallen.contains(b)) {
+                    fallen.insert(above);
+                    queue.push_back(above);
+                }
+            }
+        }
+
+        fallen.remove(&brick);
+        fallen.into_iter().sorted().collect()
+    }
+}
+
+impl Brick {
+    // All (x, y) covered by the brick
+    fn area(&self) -> impl Iterator<Item = (usize, usize)> {
+        (self.from[0]..=self.to[0]).cartesian_product(self.from[1]..=self.to[1])
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Brick>> {
+    each_line(input, |line| parse_brick(input, line))
+}
+
+// Brick like "1,0,1~1,2,1"
+fn parse_brick(input: &str, line: &str) -> Result<Brick> {
+    let (from, to) = split_once_at(input, line, "~")?;
+    let (from, to) = (parse_cube(input, from)?, parse_cube(input, to)?);
+    if (0..3).any(|i| from[i] > to[i]) {
+        return Err(Error::at(
+            input,
+            line,
+            "The first end of a brick must not be beyond the second one",
+        ));
+    }
+    if from[2] == 0 {
+        return Err(Error::at(input, line, "Bricks have to be above the ground at z = 0"));
+    }
+
+    Ok(Brick { from, to })
+}
+
+fn parse_cube(input: &str, cube: &str) -> Result<[usize; 3]> {
+    match separated(cube, ",", |c| parse_at(input, c))?[..] {
+        [x, y, z] => Ok([x, y, z]),
+        _ => Err(Error::at(input, cube, "Expected coordinates like \"1,0,1\"")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn example_part_one() {
+        let res = super::count_safely_removable(&super::settle(&super::parse_input(include_str!("example")).unwrap()));
+        assert_eq!(5, res);
+    }
+
+    #[test]
+    fn example_part_two() {
+        let res =
+            super::count_chain_reaction_falls(&super::settle(&super::parse_input(include_str!("example")).unwrap()));
+        assert_eq!(7, res);
+    }
+
+    #[test]
+    fn should_expose_support_graph() {
+        let graph = super::settle(&super::parse_input(include_str!("example")).unwrap());
+        // A supports B and C, which both support D and E
+        assert_eq!(&[1, 2], graph.supports(0));
+        assert_eq!(&[1, 2], graph.supported_by(3));
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], graph.falling_if_removed(0));
+        assert_eq!(vec![6], graph.falling_if_removed(5));
+        assert!(graph.falling_if_removed(1).is_empty());
+    }
+
+    #[test]
+    fn should_solve_example_through_solution() {
+        use crate::solution::DynSolution;
+
+        let report = super::Day22.run(include_str!("example"));
+        assert_eq!(Ok(String::from("5")), report.parts[0].answer);
+        assert_eq!(Ok(String::from("7")), report.parts[1].answer);
+    }
+
+    #[test]
+    fn should_locate_reversed_brick() {
+        let error = super::parse_input("1,0,1~1,2,1\n0,0,3~0,0,2").err().unwrap();
+        assert_eq!(
+            "line 2, column 1: The first end of a brick must not be beyond the second one",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::error::{Error, Result};
+use crate::solution::Solution;
+use crate::utils::Direction::{Down, Left, Right, Up};
+use crate::utils::{Coord, Direction, Grid};
+
+// Junctions are tracked as bits of a u64 while searching
+const MAX_JUNCTIONS: usize = 64;
+
+// Map of the forest, with the start and end tile
+pub type Trails = (Grid<char>, Coord, Coord);
+
+// Junctions with the trails leading from each of them, as (junction, length)
+type JunctionGraph = Vec<Vec<(usize, usize)>>;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "A Long Walk";
+
+    type Input = Trails;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Trails> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, input: &Trails) -> Result<usize> {
+        find_longest_hike(input, true)
+    }
+
+    fn part_two(&self, input: &Trails) -> Result<usize> {
+        find_longest_hike(input, false)
+    }
+}
+
+// Finding the longest path is NP-hard, so all hikes are searched. Most of the trails are corridors without any
+// choice though, so the grid is compressed into a graph of the few junctions first, which keeps the search feasible.
+pub fn find_longest_hike(trails: &Trails, slippery: bool) -> Result<usize> {
+    let (junctions, graph) = compress(trails, slippery);
+    if junctions.len() > MAX_JUNCTIONS {
+        return Err(Error::new(format!("There are more than {MAX_JUNCTIONS} junctions")));
+    }
+
+    // The start and end are the first two junctions
+    longest_from(&graph, 0, 1, 1).ok_or_else(|| Error::new("There is no hike from the start to the end"))
+}
+
+// Longest hike from the junction to the end, not visiting any junction twice
+fn longest_from(graph: &JunctionGraph, junction: usize, end: usize, visited: u64) -> Option<usize> {
+    if junction == end {
+        return Some(0);
+    }
+
+    graph[junction]
+        .iter()
+        .filter(|&&(next, _)| visited & (1 << next) == 0)
+        .filter_map(|&(next, length)| Some(length + longest_from(graph, next, end, visited | (1 << next))?))
+        .max()
+}
+
+// Junctions are the start, the end and all tiles with more than two paths around them. Trails lead from one junction
+// to the next without any choice, unless they end in a dead end or against a slope.
+fn compress((grid, start, end): &Trails, slippery: bool) -> (Vec<Coord>, JunctionGraph) {
+    let paths = |pos: Coord| grid.neighbours(pos).filter(|&next| grid[next] != '#');
+    let junctions = [*start, *end]
+        .into_iter()
+        .chain(
+            grid.positions()
+                .filter(|&pos| grid[pos] != '#' && paths(pos).count() > 2),
+        )
+        .collect_vec();
+    let indices: HashMap<Coord, usize> = junctions.iter().enumerate().map(|(i, &pos)| (pos, i)).collect();
+
+    let can_step = |from: Coord, dir: Direction| {
+        let to = grid.step(from, dir).filter(|&to| grid[to] != '#')?;
+        let downhill = |pos: Coord| slope(grid[pos]).is_none_or(|slope| slope == dir);
+        (!slippery || (downhill(from) && downhill(to))).then_some(to)
+    };
+    let follow_trail = |junction: Coord, dir: Direction| {
+        let (mut prev, mut pos) = (junction, can_step(junction, dir)?);
+        let mut length = 1;
+        while !indices.contains_key(&pos) {
+            let next = Direction::ALL
+                .into_iter()
+                .filter_map(|dir| can_step(pos, dir))
+                .find(|&next| next != prev)?;
+            (prev, pos) = (pos, next);
+            length += 1;
+        }
+        Some((indices[&pos], length))
+    };
+
+    let graph = junctions
+        .iter()
+        .map(|&junction| {
+            Direction::ALL
+                .into_iter()
+                .filter_map(|dir| follow_trail(junction, dir))
+                .collect()
+        })
+        .collect();
+    (junctions, graph)
+}
+
+fn slope(tile: char) -> Option<Direction> {
+    match tile {
+        '^' => Some(Up),
+        '>' => Some(Right),
+        'v' => Some(Down),
+        '<' => Some(Left),
+        _ => None,
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Trails> {
+    let grid = Grid::parse(input, input, |c| "#.^>v<".contains(c).then_some(c))?;
+    let single_path = |ri: usize, name: &str| match grid.row(ri).iter().positions(|&c| c == '.').collect_vec()[..] {
+        [ci] => Ok((ri, ci)),
+        _ => Err(Error::at_position(
+            ri + 1,
+            1,
+            format!("Expected a single path as {name}"),
+        )),
+    };
+    let start = single_path(0, "start")?;
+    let end = single_path(grid.row_count() - 1, "end")?;
+    if start == end {
+        return Err(Error::new("The start and end have to be in different rows"));
+    }
+
+    Ok((grid, start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn example_part_one() {
+        let res = super::find_longest_hike(&super::parse_input(include_str!("example")).unwrap(), true);
+        assert_eq!(94, res.unwrap());
+    }
+
+    #[test]
+    fn example_part_two() {
+        let res = super::find_longest_hike(&super::parse_input(include_str!("example")).unwrap(), false);
+        assert_eq!(154, res.unwrap());
+    }
+
+    #[test]
+    fn should_compress_to_junctions() {
+        let (junctions, graph) = super::compress(&super::parse_input(include_str!("example")).unwrap(), false);
+        // Start, end and seven junctions in between
+        assert_eq!(9, junctions.len());
+        assert_eq!((5, 3), junctions[3]);
+        assert_eq!(vec![(3, 15)], graph[0]);
+    }
+
+    #[test]
+    fn should_fail_without_hike() {
+        let trails = super::parse_input("#.###\n#.<.#\n###.#").unwrap();
+        assert!(super::find_longest_hike(&trails, true).is_err());
+        assert_eq!(4, super::find_longest_hike(&trails, false).unwrap());
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error while loading, parsing or solving the input of a day.
+/// Errors about a specific part of the input know its position, the file is attached once it is known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    message: String,
+    file: Option<String>,
+    position: Option<Position>,
+}
+
+/// Line and column within an input, both starting at 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Error {
+        Error {
+            message: message.into(),
+            file: None,
+            position: None,
+        }
+    }
+
+    /// Error about `part`, located at its position within `input`.
+    /// `part` has to be a slice of `input`, e.g. a line or a token of it, otherwise there is no position.
+    pub fn at(input: &str, part: &str, message: impl Into<String>) -> Error {
+        let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        Error {
+            position: (offset <= input.len() && part.len() <= input.len() - offset)
+                .then(|| Position::of(input, offset)),
+            ..Error::new(message)
+        }
+    }
+
+    /// Error at the given line and column of the input, both starting at 1
+    pub fn at_position(line: usize, column: usize, message: impl Into<String>) -> Error {
+        Error {
+            position: Some(Position { line, column }),
+            ..Error::new(message)
+        }
+    }
+
+    /// Moves a position found by parsing a single line on its own to that line of the whole input
+    pub fn on_line(mut self, line: usize) -> Error {
+        if let Some(position) = &mut self.position {
+            position.line = line;
+        }
+        self
+    }
+
+    /// Attaches the file the input was read from, unless there already is one
+    pub fn in_file(mut self, file: impl Into<String>) -> Error {
+        self.file.get_or_insert_with(|| file.into());
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn position(&self) -> Option<Position> {
+        self.position
+    }
+}
+
+impl Position {
+    fn of(input: &str, offset: usize) -> Position {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.file, self.position) {
+            (Some(file), Some(p)) => write!(f, "{}:{}:{}: {}", file, p.line, p.column, self.message),
+            (None, Some(p)) => write!(f, "line {}, column {}: {}", p.line, p.column, self.message),
+            (Some(file), None) => write!(f, "{}: {}", file, self.message),
+            (None, None) => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Position};
+
+    #[test]
+    fn should_locate_part_of_input() {
+        let input = "Game 1: 3 blue\nGame 2: x red";
+        let token = &input[23..24];
+        let error = Error::at(input, token, "Invalid number");
+        assert_eq!(Some(Position { line: 2, column: 9 }), error.position());
+        assert_eq!("line 2, column 9: Invalid number", error.to_string());
+        assert_eq!(
+            "src/d02/input:2:9: Invalid number",
+            error.in_file("src/d02/input").to_string()
+        );
+    }
+
+    #[test]
+    fn should_not_locate_foreign_part() {
+        let other = String::from("x");
+        let error = Error::at("Game 1: 3 blue", &other, "Invalid number");
+        assert_eq!(None, error.position());
+        assert_eq!("Invalid number", error.to_string());
+    }
+}
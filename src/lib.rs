@@ -0,0 +1,69 @@
+//! Solutions for [Advent of code 2023](https://adventofcode.com/2023).
+//!
+//! Every day is a module exposing a [Solution](solution::Solution), which parses the puzzle input text and solves
+//! both parts on it. All days are registered in [SOLUTIONS]. Malformed input results in an [Error](error::Error)
+//! pointing to its position.
+//!
+//! ```
+//! use aoc_2023::d01::Day01;
+//! use aoc_2023::solution::Solution;
+//!
+//! let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+//! assert_eq!(142, Day01.solve_part_one(input).unwrap());
+//! ```
+
+use crate::solution::DynSolution;
+
+pub mod d01;
+pub mod d02;
+pub mod d03;
+pub mod d04;
+pub mod d05;
+pub mod d06;
+pub mod d07;
+pub mod d08;
+pub mod d09;
+pub mod d10;
+pub mod d11;
+pub mod d12;
+pub mod d13;
+pub mod d14;
+pub mod d15;
+pub mod d16;
+pub mod d17;
+pub mod d18;
+pub mod d19;
+pub mod d20;
+pub mod d21;
+pub mod d22;
+pub mod d23;
+pub mod error;
+pub mod solution;
+pub mod utils;
+
+// Every day has to be registered here, in order, to be picked up by the runner
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &d01::Day01,
+    &d02::Day02,
+    &d03::Day03,
+    &d04::Day04,
+    &d05::Day05,
+    &d06::Day06,
+    &d07::Day07,
+    &d08::Day08,
+    &d09::Day09,
+    &d10::Day10,
+    &d11::Day11,
+    &d12::Day12,
+    &d13::Day13,
+    &d14::Day14,
+    &d15::Day15,
+    &d16::Day16,
+    &d17::Day17,
+    &d18::Day18,
+    &d19::Day19,
+    &d20::Day20,
+    &d21::Day21,
+    &d22::Day22,
+    &d23::Day23,
+];
@@ -1,70 +1,117 @@
 use std::env::args;
-use std::time::Instant;
+use std::process::exit;
+use std::time::{Duration, Instant};
 
-mod d01;
-mod d02;
-mod d03;
-mod d04;
-mod d05;
-mod d06;
-mod d07;
-mod d08;
-mod d09;
-mod d10;
-mod d11;
-mod d12;
-mod d13;
-mod d14;
-mod d15;
-mod d16;
-mod d17;
-mod d18;
-mod utils;
+use aoc_2023::solution::{DayReport, DynSolution};
+use aoc_2023::{utils, SOLUTIONS};
+
+use crate::cli::{Args, Command};
+use crate::report::{Format, Record};
+use crate::selector::SelectedDay;
+
+mod bench;
+mod cli;
+mod report;
+mod runner;
+mod selector;
+mod verify;
 
 fn main() {
-    let arg_values = args().collect::<Vec<_>>();
-    let first_arg = arg_values.get(1).expect("Provide the module as arg, e.g. d01");
-    match first_arg.as_str() {
-        "all" => {
-            let start = Instant::now();
-            d01::main();
-            d02::main();
-            d03::main();
-            d04::main();
-            d05::main();
-            d06::main();
-            d07::main();
-            d08::main();
-            d09::main();
-            d10::main();
-            d11::main();
-            d12::main();
-            d13::main();
-            d14::main();
-            d15::main();
-            d16::main();
-            d17::main();
-            d18::main();
+    let args = Args::parse(args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        exit(2);
+    });
+    if let Some(input_dir) = &args.input_dir {
+        utils::set_input_root(input_dir);
+    }
+    match &args.command {
+        Command::Bench(options) => {
+            if !bench::bench(options) {
+                exit(1);
+            }
+        }
+        Command::Verify => {
+            if !verify::verify() {
+                exit(1);
+            }
+        }
+        Command::Run(selectors) => {
+            let days = selector::select(selectors, SOLUTIONS).unwrap_or_else(|err| {
+                eprintln!("{err}");
+                exit(2);
+            });
+            if !run(&days, &args) {
+                exit(1);
+            }
+        }
+    }
+}
+
+/// Runs the given parts of each day and prints their reports, returns whether all of them succeeded
+fn run(days: &[SelectedDay], args: &Args) -> bool {
+    let start = Instant::now();
+    let mut records = Vec::new();
+    let mut failures = 0;
+    // Sum of the time spent in each day, approximating the CPU time when running days concurrently.
+    // It overestimates it when there are more jobs than available cores.
+    let mut cpu_time = Duration::ZERO;
+    runner::run_ordered(days, &args.input, args.jobs, args.stream, |solution, report| {
+        cpu_time += match report.parts.first() {
+            // The parts of a single pass share its time
+            Some(part) if report.single_pass => part.solve_time,
+            _ => report.parse_time + report.parts.iter().map(|p| p.solve_time).sum::<Duration>(),
+        };
+        failures += report.has_failures() as usize;
+        match args.format {
+            Format::Text => print_report(solution, &report),
+            Format::Json | Format::Csv => records.extend(Record::from_report(solution, &report)),
+        }
+    });
+
+    match args.format {
+        Format::Text if days.len() > 1 => {
             println!("\nTotal execution time: {:.2?}", start.elapsed());
+            println!("Summed time of all days: {:.2?}", cpu_time);
+        }
+        Format::Text => (),
+        Format::Json => println!("{}", report::to_json(&records)),
+        Format::Csv => print!("{}", report::to_csv(&records)),
+    }
+    if failures > 0 {
+        eprintln!("{} day(s) failed", failures);
+    }
+    failures == 0
+}
+
+fn print_report(solution: &dyn DynSolution, report: &DayReport) {
+    println!("==== DAY {}: {} ====", solution.day(), solution.title());
+    if report.skipped {
+        if let Some(Err(reason)) = report.parts.first().map(|p| &p.answer) {
+            println!("Skipped: {}", reason);
+        }
+        return;
+    }
+    if !report.single_pass {
+        println!("Parsed in {:.2?}", report.parse_time);
+    }
+    for part in &report.parts {
+        println!("**** PART {} ****", part.part);
+        match &part.answer {
+            Ok(answer) => println!("Result: {}", answer),
+            Err(error) => println!("Failed: {}", error),
+        }
+        if !report.single_pass {
+            println!("Finished in {:.2?}", part.solve_time);
+            if part.cache.lookups() > 0 {
+                println!("Cache: {}", part.cache);
+            }
+        }
+    }
+    if let Some(part) = report.parts.first().filter(|_| report.single_pass) {
+        println!("**** SINGLE PASS ****");
+        println!("Solved in {:.2?}", part.solve_time);
+        if part.cache.lookups() > 0 {
+            println!("Cache: {}", part.cache);
         }
-        "d01" => d01::main(),
-        "d02" => d02::main(),
-        "d03" => d03::main(),
-        "d04" => d04::main(),
-        "d05" => d05::main(),
-        "d06" => d06::main(),
-        "d07" => d07::main(),
-        "d08" => d08::main(),
-        "d09" => d09::main(),
-        "d10" => d10::main(),
-        "d11" => d11::main(),
-        "d12" => d12::main(),
-        "d13" => d13::main(),
-        "d14" => d14::main(),
-        "d15" => d15::main(),
-        "d16" => d16::main(),
-        "d17" => d17::main(),
-        "d18" => d18::main(),
-        _ => println!("Invalid argument: {}", first_arg),
     }
 }
@@ -0,0 +1,194 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_2023::solution::{DayReport, DynSolution};
+use aoc_2023::utils::CacheStats;
+
+/// Output format of the results of a run
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Invalid format {s}, expected text, json or csv")),
+        }
+    }
+}
+
+/// Result of a single part of a day, as emitted in machine-readable formats
+pub struct Record {
+    pub day: String,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    pub cache: CacheStats,
+    /// Whether both parts were solved in a single pass, whose time and cache statistics are shared by both records
+    pub single_pass: bool,
+    pub status: &'static str,
+    /// Error message in case the part failed
+    pub error: Option<String>,
+}
+
+impl Record {
+    pub fn from_report(solution: &dyn DynSolution, report: &DayReport) -> Vec<Record> {
+        report
+            .parts
+            .iter()
+            .map(|part| Record {
+                day: solution.name(),
+                part: part.part,
+                answer: part.answer.clone().ok(),
+                parse_time: report.parse_time,
+                solve_time: part.solve_time,
+                cache: part.cache,
+                single_pass: report.single_pass,
+                status: match &part.answer {
+                    _ if report.skipped => "skipped",
+                    Ok(_) => "ok",
+                    Err(_) => "failed",
+                },
+                error: part.answer.as_ref().err().map(ToString::to_string),
+            })
+            .collect()
+    }
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    let lines = records
+        .iter()
+        .map(|r| {
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \
+                 \"cache_hits\": {}, \"cache_misses\": {}, \"single_pass\": {}, \"status\": {}, \"error\": {}}}",
+                json_string(&r.day),
+                r.part,
+                r.answer.as_deref().map_or(String::from("null"), json_string),
+                r.parse_time.as_nanos(),
+                r.solve_time.as_nanos(),
+                r.cache.hits,
+                r.cache.misses,
+                r.single_pass,
+                json_string(r.status),
+                r.error.as_deref().map_or(String::from("null"), json_string)
+            )
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return String::from("[]");
+    }
+    format!("[\n{}\n]", lines.join(",\n"))
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut csv = String::from("day,part,answer,parse_ns,solve_ns,cache_hits,cache_misses,single_pass,status,error\n");
+    for r in records {
+        csv += &format!(
+            "{},{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&r.day),
+            r.part,
+            csv_field(r.answer.as_deref().unwrap_or_default()),
+            r.parse_time.as_nanos(),
+            r.solve_time.as_nanos(),
+            r.cache.hits,
+            r.cache.misses,
+            r.single_pass,
+            csv_field(r.status),
+            csv_field(r.error.as_deref().unwrap_or_default())
+        );
+    }
+    csv
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            '\r' => escaped += "\\r",
+            '\t' => escaped += "\\t",
+            c if (c as u32) < 0x20 => escaped += &format!("\\u{:04x}", c as u32),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+// Fields containing separators, quotes or line breaks are quoted, with quotes doubled
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_2023::utils::CacheStats;
+
+    use super::{to_csv, to_json, Record};
+
+    fn record(answer: &str) -> Record {
+        Record {
+            day: String::from("d01"),
+            part: 2,
+            answer: Some(String::from(answer)),
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Duration::from_micros(20),
+            cache: CacheStats { hits: 3, misses: 4 },
+            single_pass: false,
+            status: "ok",
+            error: None,
+        }
+    }
+
+    fn failed_record(error: &str) -> Record {
+        Record {
+            answer: None,
+            status: "failed",
+            error: Some(String::from(error)),
+            ..record("")
+        }
+    }
+
+    #[test]
+    fn should_write_json() {
+        let expected = "[\n  {\"day\": \"d01\", \"part\": 2, \"answer\": \"a\\\"b\", \"parse_ns\": 1500, \
+                        \"solve_ns\": 20000, \"cache_hits\": 3, \"cache_misses\": 4, \"single_pass\": false, \
+                        \"status\": \"ok\", \"error\": null},\n  \
+                        {\"day\": \"d01\", \"part\": 2, \"answer\": null, \"parse_ns\": 1500, \
+                        \"solve_ns\": 20000, \"cache_hits\": 3, \"cache_misses\": 4, \"single_pass\": true, \
+                        \"status\": \"failed\", \"error\": \"boom\"}\n]";
+        let failed = Record {
+            single_pass: true,
+            ..failed_record("boom")
+        };
+        assert_eq!(expected, to_json(&[record("a\"b"), failed]));
+        assert_eq!("[]", to_json(&[]));
+    }
+
+    #[test]
+    fn should_write_csv() {
+        let expected = "day,part,answer,parse_ns,solve_ns,cache_hits,cache_misses,single_pass,status,error\n\
+                        d01,2,\"1,\"\"2\"\"\",1500,20000,3,4,false,ok,\n\
+                        d01,2,,1500,20000,3,4,false,failed,boom\n";
+        assert_eq!(expected, to_csv(&[record("1,\"2\""), failed_record("boom")]));
+    }
+}
@@ -0,0 +1,148 @@
+use std::fs::{self, File};
+use std::io::{stdin, BufReader, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use aoc_2023::error::{Error, Result};
+use aoc_2023::solution::{DayReport, DynSolution};
+use aoc_2023::utils::{self, InputLines};
+
+use crate::cli::InputSource;
+use crate::selector::SelectedDay;
+
+pub fn read_input(solution: &dyn DynSolution, input_source: &Option<InputSource>) -> Result<String> {
+    match input_source {
+        None => utils::get_input(&default_input(solution)),
+        Some(InputSource::Stdin) => {
+            let mut input = String::new();
+            stdin()
+                .read_to_string(&mut input)
+                .map_err(|err| Error::new(format!("Cannot read input from stdin: {err}")))?;
+            Ok(input)
+        }
+        Some(InputSource::File(path)) => {
+            fs::read_to_string(path).map_err(|err| Error::new(format!("Cannot read input {}: {err}", path.display())))
+        }
+    }
+}
+
+/// Opens the input to read it line by line, instead of reading all of it at once
+pub fn read_input_lines(solution: &dyn DynSolution, input_source: &Option<InputSource>) -> Result<InputLines<'static>> {
+    match input_source {
+        None => utils::read_lines(&default_input(solution)),
+        Some(InputSource::Stdin) => Ok(utils::lines_of(stdin().lock())),
+        Some(InputSource::File(path)) => File::open(path)
+            .map(|file| utils::lines_of(BufReader::new(file)))
+            .map_err(|err| Error::new(format!("Cannot read input {}: {err}", path.display()))),
+    }
+}
+
+fn default_input(solution: &dyn DynSolution) -> String {
+    format!("{}/input", solution.name())
+}
+
+/// Reason to skip the day when running it on its own input, i.e. that there is none yet
+pub fn missing_input(solution: &dyn DynSolution) -> Option<Error> {
+    let default_input = default_input(solution);
+    (!utils::has_input(&default_input))
+        .then(|| Error::new(format!("No input at {}", utils::input_path(&default_input).display())))
+}
+
+// Days without an input of their own are skipped, unless another input was given
+fn skip_without_input(
+    solution: &dyn DynSolution,
+    parts: &[u8],
+    input_source: &Option<InputSource>,
+) -> Option<DayReport> {
+    match input_source {
+        None => missing_input(solution).map(|reason| DayReport::skipped(reason, parts)),
+        Some(_) => None,
+    }
+}
+
+// Name of the input in error messages
+fn input_name(solution: &dyn DynSolution, input_source: &Option<InputSource>) -> String {
+    match input_source {
+        None => utils::input_path(&default_input(solution)).display().to_string(),
+        Some(InputSource::Stdin) => String::from("<stdin>"),
+        Some(InputSource::File(path)) => path.display().to_string(),
+    }
+}
+
+/// Reads the input and runs the given parts of each day on it in isolation, using up to `jobs` threads for
+/// independent days. With `stream`, the input is read line by line, see [stream_day]. Reports are passed to
+/// `on_report` in the order of the given days, as soon as all previous days are done.
+pub fn run_ordered(
+    days: &[SelectedDay],
+    input_source: &Option<InputSource>,
+    jobs: usize,
+    stream: bool,
+    mut on_report: impl FnMut(&dyn DynSolution, DayReport),
+) {
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+            scope.spawn(move || loop {
+                let i = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some((solution, parts)) = days.get(i) else { break };
+                let report = if stream {
+                    stream_day(*solution, parts, input_source)
+                } else {
+                    run_day(*solution, parts, input_source)
+                };
+                let _ = sender.send((i, report));
+            });
+        }
+        drop(sender);
+
+        // Days might finish out of order, so hold back reports until all previous ones were passed on
+        let mut pending: Vec<Option<DayReport>> = days.iter().map(|_| None).collect();
+        let mut next_to_report = 0;
+        for (i, report) in receiver {
+            pending[i] = Some(report);
+            while let Some(report) = pending.get_mut(next_to_report).and_then(Option::take) {
+                on_report(days[next_to_report].0, report);
+                next_to_report += 1;
+            }
+        }
+    });
+}
+
+/// Reads the input and runs the given parts of the day on it. Failing to read the input fails all of them, a day
+/// without its own input is skipped.
+pub fn run_day(solution: &dyn DynSolution, parts: &[u8], input_source: &Option<InputSource>) -> DayReport {
+    if let Some(report) = skip_without_input(solution, parts, input_source) {
+        return report;
+    }
+    match read_input(solution, input_source) {
+        Ok(input) => solution
+            .run_parts(&input, parts)
+            .in_file(&input_name(solution, input_source)),
+        Err(error) => DayReport::failed(error, parts),
+    }
+}
+
+/// Runs the given parts of the day in a single pass over the input lines, without holding all of them in memory.
+/// Days that need their whole input at once are run like [run_day] instead.
+pub fn stream_day(solution: &dyn DynSolution, parts: &[u8], input_source: &Option<InputSource>) -> DayReport {
+    if let Some(report) = skip_without_input(solution, parts, input_source) {
+        return report;
+    }
+    let mut lines = match read_input_lines(solution, input_source) {
+        Ok(lines) => lines,
+        Err(error) => return DayReport::failed(error, parts),
+    };
+    match solution.run_lines(&mut lines, parts) {
+        Some(report) => report.in_file(&input_name(solution, input_source)),
+        None => {
+            // Nothing was read yet, so the input can still be read as a whole
+            drop(lines);
+            run_day(solution, parts, input_source)
+        }
+    }
+}
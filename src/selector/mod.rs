@@ -0,0 +1,155 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use aoc_2023::solution::DynSolution;
+
+const ALL_DAYS: RangeInclusive<u8> = 1..=25;
+const ALL_PARTS: [u8; 2] = [1, 2];
+
+/// A solution together with the parts of it to run
+pub type SelectedDay<'a> = (&'a dyn DynSolution, Vec<u8>);
+
+/// Selects a range of days and optionally only one of their parts,
+/// e.g. `all`, `d05`, `d03..d07`, `d05:2` or `d03..d07:1`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Selector {
+    pub days: RangeInclusive<u8>,
+    pub part: Option<u8>,
+}
+
+impl Selector {
+    /// Parses a comma separated list of selectors, e.g. `d01,d14,d17:1`
+    pub fn parse_list(list: &str) -> Result<Vec<Selector>, String> {
+        list.split(',').map(str::parse).collect()
+    }
+
+    fn matches(&self, day: u8, part: u8) -> bool {
+        self.days.contains(&day) && self.part.is_none_or(|p| p == part)
+    }
+}
+
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid selector {s}, expected e.g. all, d05, d03..d07 or d05:2");
+        let (days, part) = match s.split_once(':') {
+            Some((days, part)) => match part.parse::<u8>() {
+                Ok(part) if ALL_PARTS.contains(&part) => (days, Some(part)),
+                _ => return Err(invalid()),
+            },
+            None => (s, None),
+        };
+        let day = |d: &str| {
+            d.strip_prefix('d')
+                .and_then(|d| d.parse::<u8>().ok())
+                .ok_or_else(invalid)
+        };
+        let days = match days.split_once("..") {
+            _ if days == "all" => ALL_DAYS,
+            Some((from, to)) => day(from)?..=day(to)?,
+            None => day(days)?..=day(days)?,
+        };
+        if days.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Selector { days, part })
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.days.start(), self.days.end()) {
+            _ if self.days == ALL_DAYS => write!(f, "all")?,
+            (from, to) if from == to => write!(f, "d{:02}", from)?,
+            (from, to) => write!(f, "d{:02}..d{:02}", from, to)?,
+        }
+        match self.part {
+            Some(part) => write!(f, ":{}", part),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The single day all selectors refer to, if there is only one
+pub fn single_day(selectors: &[Selector]) -> Option<u8> {
+    let day = *selectors.first()?.days.start();
+    selectors.iter().all(|s| s.days == (day..=day)).then_some(day)
+}
+
+/// Picks the solutions and their parts chosen by any of the selectors, in order of the days.
+/// Fails for a selector that does not match any of the solutions.
+pub fn select<'a>(selectors: &[Selector], solutions: &[&'a dyn DynSolution]) -> Result<Vec<SelectedDay<'a>>, String> {
+    if let Some(selector) = selectors
+        .iter()
+        .find(|s| !solutions.iter().any(|solution| s.days.contains(&solution.day())))
+    {
+        return Err(format!("No solution for {selector}"));
+    }
+    Ok(solutions
+        .iter()
+        .map(|&solution| {
+            let parts = ALL_PARTS
+                .into_iter()
+                .filter(|&part| selectors.iter().any(|s| s.matches(solution.day(), part)))
+                .collect::<Vec<_>>();
+            (solution, parts)
+        })
+        .filter(|(_, parts)| !parts.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_2023::SOLUTIONS;
+
+    use super::{select, single_day, Selector};
+
+    fn selector(s: &str) -> Selector {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn should_parse_selectors() {
+        let expected = |days, part| Selector { days, part };
+        assert_eq!(expected(1..=25, None), selector("all"));
+        assert_eq!(expected(5..=5, None), selector("d05"));
+        assert_eq!(expected(5..=5, Some(2)), selector("d05:2"));
+        assert_eq!(expected(3..=7, None), selector("d03..d07"));
+        assert_eq!(expected(3..=7, Some(1)), selector("d03..d07:1"));
+        assert_eq!(3, Selector::parse_list("d01,d14,d17:1").unwrap().len());
+    }
+
+    #[test]
+    fn should_reject_invalid_selectors() {
+        for invalid in ["", "5", "d", "dx", "d05:3", "d05:", "d07..d03", "d03..", "all:x"] {
+            assert!(invalid.parse::<Selector>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn should_display_selectors() {
+        for s in ["all", "d05", "d05:2", "d03..d07:1"] {
+            assert_eq!(s, selector(s).to_string());
+        }
+    }
+
+    #[test]
+    fn should_find_single_day() {
+        assert_eq!(Some(5), single_day(&[selector("d05:1"), selector("d05:2")]));
+        assert_eq!(None, single_day(&[selector("d05"), selector("d06")]));
+        assert_eq!(None, single_day(&[selector("d05..d06")]));
+    }
+
+    #[test]
+    fn should_select_days_and_parts() {
+        let selected = select(&Selector::parse_list("d17:1,d01,d14").unwrap(), SOLUTIONS).unwrap();
+        let selected = selected
+            .iter()
+            .map(|(solution, parts)| (solution.day(), parts.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(1, vec![1, 2]), (14, vec![1, 2]), (17, vec![1])], selected);
+        assert!(select(&[selector("d99")], SOLUTIONS).is_err());
+    }
+}
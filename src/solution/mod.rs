@@ -0,0 +1,277 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::utils::{catch_panic, normalize_input, take_cache_stats, CacheStats};
+
+/// Solves both parts of the puzzle of a single day.
+/// The input text is parsed once and then shared by both parts.
+pub trait Solution: Sync {
+    /// Day of the advent calendar, e.g. `1` for `d01`
+    const DAY: u8;
+    /// Puzzle title as shown on adventofcode.com
+    const TITLE: &'static str;
+
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(&self, input: &str) -> Result<Self::Input>;
+    fn part_one(&self, input: &Self::Input) -> Result<Self::PartOne>;
+    fn part_two(&self, input: &Self::Input) -> Result<Self::PartTwo>;
+
+    /// Solves both parts in a single pass over the lines of the input, holding only a few of them in memory at once.
+    /// Only days folding over their lines implement it, the others return `None` without reading any line.
+    fn solve_lines(
+        &self,
+        _lines: &mut dyn Iterator<Item = Result<String>>,
+    ) -> Option<Result<(Self::PartOne, Self::PartTwo)>> {
+        None
+    }
+
+    /// Parses the input text, normalized with [normalize_input], and solves part one on it
+    fn solve_part_one(&self, input: &str) -> Result<Self::PartOne> {
+        self.part_one(&self.parse(&normalize_input(input))?)
+    }
+
+    /// Parses the input text, normalized with [normalize_input], and solves part two on it
+    fn solve_part_two(&self, input: &str) -> Result<Self::PartTwo> {
+        self.part_two(&self.parse(&normalize_input(input))?)
+    }
+}
+
+pub struct PartReport {
+    pub part: u8,
+    /// The answer, or the error in case parsing or solving failed or panicked
+    pub answer: Result<String>,
+    pub solve_time: Duration,
+    /// Hits and misses of the memos used while solving the part
+    pub cache: CacheStats,
+}
+
+pub struct DayReport {
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+    /// Whether the day was not run at all, e.g. because it has no input yet. Its parts hold the reason as error.
+    pub skipped: bool,
+    /// Whether all parts were solved together in a single pass over the input lines, without parsing first.
+    /// The parts then share the solve time and cache statistics of that pass.
+    pub single_pass: bool,
+}
+
+impl DayReport {
+    /// Report for a day that could not be run at all, e.g. because its input could not be read
+    pub fn failed(error: Error, parts: &[u8]) -> DayReport {
+        DayReport {
+            skipped: false,
+            ..DayReport::skipped(error, parts)
+        }
+    }
+
+    /// Report for a day that was not run on purpose, which does not count as failure
+    pub fn skipped(reason: Error, parts: &[u8]) -> DayReport {
+        let parts = parts
+            .iter()
+            .map(|&part| PartReport {
+                part,
+                answer: Err(reason.clone()),
+                solve_time: Duration::ZERO,
+                cache: CacheStats::default(),
+            })
+            .collect();
+        DayReport {
+            parse_time: Duration::ZERO,
+            parts,
+            skipped: true,
+            single_pass: false,
+        }
+    }
+
+    pub fn has_failures(&self) -> bool {
+        !self.skipped && self.parts.iter().any(|p| p.answer.is_err())
+    }
+
+    /// Attaches the file the input was read from to all errors located in the input
+    pub fn in_file(mut self, file: &str) -> DayReport {
+        for part in &mut self.parts {
+            if let Err(error) = &mut part.answer {
+                if error.position().is_some() {
+                    *error = error.clone().in_file(file);
+                }
+            }
+        }
+        self
+    }
+}
+
+// Runs the given function, turning a panic into an error
+fn guarded<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    catch_panic(f).unwrap_or_else(|message| Err(panic_error(message)))
+}
+
+fn panic_error(message: String) -> Error {
+    Error::new(format!("panicked: {message}"))
+}
+
+/// Object safe counterpart of [Solution], so that days with different answer types fit into one registry.
+/// Implemented for every [Solution], there is no need to implement it by hand.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+
+    /// Parses the input text, normalized with [normalize_input], and solves the given parts on it, timing each
+    /// phase separately.
+    /// Errors and panics are reported as failure of the affected parts.
+    fn run_parts(&self, input: &str, parts: &[u8]) -> DayReport;
+
+    /// Solves the given parts in a single pass over the input lines, see [Solution::solve_lines].
+    /// The time and cache statistics of that pass are reported for each part. `None` if the day cannot be solved
+    /// this way.
+    fn run_lines(&self, lines: &mut dyn Iterator<Item = Result<String>>, parts: &[u8]) -> Option<DayReport>;
+
+    /// Parses the input text and solves both parts on it, see [DynSolution::run_parts]
+    fn run(&self, input: &str) -> DayReport {
+        self.run_parts(input, &[1, 2])
+    }
+
+    /// Module name of the day, e.g. `d01`
+    fn name(&self) -> String {
+        format!("d{:02}", self.day())
+    }
+}
+
+impl<S: Solution> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn run_parts(&self, input: &str, parts: &[u8]) -> DayReport {
+        let start = Instant::now();
+        let input = guarded(|| self.parse(&normalize_input(input)));
+        let parse_time = start.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                // Drops statistics of memos used before, e.g. while parsing
+                take_cache_stats();
+                let start = Instant::now();
+                let answer = input.as_ref().map_err(Error::clone).and_then(|input| {
+                    guarded(|| match part {
+                        1 => self.part_one(input).map(|answer| answer.to_string()),
+                        _ => self.part_two(input).map(|answer| answer.to_string()),
+                    })
+                });
+                PartReport {
+                    part,
+                    answer,
+                    solve_time: start.elapsed(),
+                    cache: take_cache_stats(),
+                }
+            })
+            .collect();
+
+        DayReport {
+            parse_time,
+            parts,
+            skipped: false,
+            single_pass: false,
+        }
+    }
+
+    fn run_lines(&self, lines: &mut dyn Iterator<Item = Result<String>>, parts: &[u8]) -> Option<DayReport> {
+        take_cache_stats();
+        let start = Instant::now();
+        let answers = match catch_panic(|| self.solve_lines(lines)) {
+            Ok(None) => return None,
+            Ok(Some(answers)) => answers,
+            Err(message) => Err(panic_error(message)),
+        };
+        let solve_time = start.elapsed();
+        let cache = take_cache_stats();
+
+        let parts = parts
+            .iter()
+            .map(|&part| PartReport {
+                part,
+                answer: match &answers {
+                    Ok((one, _)) if part == 1 => Ok(one.to_string()),
+                    Ok((_, two)) => Ok(two.to_string()),
+                    Err(error) => Err(error.clone()),
+                },
+                solve_time,
+                cache,
+            })
+            .collect();
+        Some(DayReport {
+            parse_time: Duration::ZERO,
+            parts,
+            skipped: false,
+            single_pass: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DynSolution, Solution};
+    use crate::error::{Error, Result};
+
+    struct Failing;
+
+    impl Solution for Failing {
+        const DAY: u8 = 99;
+        const TITLE: &'static str = "Failing";
+
+        type Input = u32;
+        type PartOne = u32;
+        type PartTwo = u32;
+
+        fn parse(&self, input: &str) -> Result<u32> {
+            input.parse().map_err(|_| Error::at(input, input, "Not a number"))
+        }
+
+        fn part_one(&self, input: &u32) -> Result<u32> {
+            Ok(input + 1)
+        }
+
+        fn part_two(&self, _input: &u32) -> Result<u32> {
+            panic!("part two failed")
+        }
+    }
+
+    #[test]
+    fn panic_in_part_fails_only_that_part() {
+        let report = Failing.run("41");
+        assert_eq!(Ok(String::from("42")), report.parts[0].answer);
+        assert_eq!(Err(Error::new("panicked: part two failed")), report.parts[1].answer);
+        assert!(report.has_failures());
+    }
+
+    #[test]
+    fn parse_error_fails_both_parts() {
+        let report = Failing.run("x").in_file("input");
+        for part in report.parts {
+            assert_eq!("input:1:1: Not a number", part.answer.unwrap_err().to_string());
+        }
+    }
+
+    #[test]
+    fn skipped_day_does_not_fail() {
+        let report = super::DayReport::skipped(Error::new("No input"), &[1, 2]);
+        assert_eq!(2, report.parts.len());
+        assert!(!report.has_failures());
+        assert!(super::DayReport::failed(Error::new("No input"), &[1, 2]).has_failures());
+    }
+
+    #[test]
+    fn should_only_run_given_parts() {
+        let report = Failing.run_parts("41", &[1]);
+        assert_eq!(1, report.parts.len());
+        assert!(!report.has_failures());
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Iteration the states start repeating at, and after how many steps they do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// State after iterating, together with the cycle that allowed skipping ahead, if one was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Iterated<S> {
+    pub state: S,
+    pub cycle: Option<Cycle>,
+}
+
+/// Applies `step` `iterations` times, starting with `initial`. All states are remembered, so once a state repeats,
+/// the remaining iterations are skipped by looking up the state they end in.
+pub fn iterate_with_cycle<S>(initial: S, iterations: usize, mut step: impl FnMut(&S) -> S) -> Iterated<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut history = vec![initial];
+    for i in 1..=iterations {
+        let state = step(&history[i - 1]);
+        if let Some(&start) = seen.get(&state) {
+            let length = i - start;
+            let state = history.swap_remove(start + (iterations - start) % length);
+            return Iterated {
+                state,
+                cycle: Some(Cycle { start, length }),
+            };
+        }
+        seen.insert(state.clone(), i);
+        history.push(state);
+    }
+
+    Iterated {
+        state: history.pop().unwrap(),
+        cycle: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{iterate_with_cycle, Cycle};
+
+    #[test]
+    fn should_skip_ahead_in_cycle() {
+        // 0, 1, 2, 3, then repeating 4, 5, 6, 7, 8, 9
+        let res = iterate_with_cycle(0, 1_000_000_007, |&n| if n == 9 { 4 } else { n + 1 });
+        assert_eq!(Some(Cycle { start: 4, length: 6 }), res.cycle);
+        assert_eq!(4 + (1_000_000_007 - 4) % 6, res.state);
+    }
+
+    #[test]
+    fn should_iterate_without_cycle() {
+        let res = iterate_with_cycle(0, 10, |n| n + 1);
+        assert_eq!(10, res.state);
+        assert_eq!(None, res.cycle);
+        assert_eq!(0, iterate_with_cycle(0, 0, |n| n + 1).state);
+    }
+}
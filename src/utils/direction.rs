@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use Direction::{Down, Left, Right, Up};
+
+/// Position on a grid as `(row index, column index)`, rows growing downwards
+pub type Coord = (usize, usize);
+
+/// One of the four directions to move on a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting with `Up`
+    pub const ALL: [Direction; 4] = [Up, Right, Down, Left];
+
+    /// Decodes the index encoding `0` = right, `1` = down, `2` = left and `3` = up
+    pub fn from_index(index: u8) -> Option<Direction> {
+        match index {
+            0 => Some(Right),
+            1 => Some(Down),
+            2 => Some(Left),
+            3 => Some(Up),
+            _ => None,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Up => Left,
+            Right => Up,
+            Down => Right,
+            Left => Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.turn_left().turn_left()
+    }
+
+    pub fn is_opposite(self, other: Direction) -> bool {
+        self.reverse() == other
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        self == Left || self == Right
+    }
+
+    /// Change of row and column index when moving a single step
+    pub fn delta(self) -> (i64, i64) {
+        match self {
+            Up => (-1, 0),
+            Right => (0, 1),
+            Down => (1, 0),
+            Left => (0, -1),
+        }
+    }
+
+    /// Coordinate a single step away, `None` when stepping beyond the first row or column.
+    /// See [Grid::step](super::Grid::step) to also stay within the bounds of a grid.
+    pub fn step(self, (ri, ci): Coord) -> Option<Coord> {
+        match self {
+            Up => Some((ri.checked_sub(1)?, ci)),
+            Right => Some((ri, ci + 1)),
+            Down => Some((ri + 1, ci)),
+            Left => Some((ri, ci.checked_sub(1)?)),
+        }
+    }
+}
+
+/// Parses the letter encoding `U`, `R`, `D` and `L`
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" => Ok(Up),
+            "R" => Ok(Right),
+            "D" => Ok(Down),
+            "L" => Ok(Left),
+            _ => Err(format!("Invalid direction {s:?}, expected U, R, D or L")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Direction;
+    use super::Direction::{Down, Left, Right, Up};
+
+    #[test]
+    fn should_turn() {
+        assert_eq!(Left, Up.turn_left());
+        assert_eq!(Right, Up.turn_right());
+        assert_eq!(Down, Up.reverse());
+        assert!(Left.is_opposite(Right));
+        assert!(!Left.is_opposite(Up));
+        for dir in Direction::ALL {
+            assert_eq!(dir, dir.turn_left().turn_right());
+        }
+    }
+
+    #[test]
+    fn should_step_checked() {
+        assert_eq!(Some((0, 1)), Up.step((1, 1)));
+        assert_eq!(Some((1, 2)), Right.step((1, 1)));
+        assert_eq!(None, Up.step((0, 1)));
+        assert_eq!(None, Left.step((1, 0)));
+        assert_eq!((0, -1), Left.delta());
+    }
+
+    #[test]
+    fn should_parse_encodings() {
+        assert_eq!(Ok(Down), "D".parse::<Direction>());
+        assert!("X".parse::<Direction>().is_err());
+        assert_eq!(Some(Right), Direction::from_index(0));
+        assert_eq!(Some(Up), Direction::from_index(3));
+        assert_eq!(None, Direction::from_index(4));
+    }
+}
@@ -0,0 +1,204 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+use crate::utils::{Coord, Direction};
+
+/// Rectangular grid of cells, addressed by `(row index, column index)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    row_count: usize,
+    col_count: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its rows, which all have to be of the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let row_count = rows.len();
+        let col_count = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == col_count),
+            "Rows of different lengths"
+        );
+        Grid {
+            cells: rows.into_iter().flatten().collect(),
+            row_count,
+            col_count,
+        }
+    }
+
+    /// Parses each line of `target`, which has to be a slice of `input`, as a row, converting each char with `cell`.
+    /// Fails for chars `cell` does not accept and for rows of different lengths, with their position within `input`.
+    pub fn parse(input: &str, target: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>> {
+        let mut cells = Vec::with_capacity(target.len());
+        let mut row_count = 0;
+        let col_count = target.lines().next().map_or(0, |line| line.chars().count());
+        for line in target.lines() {
+            for (i, c) in line.char_indices() {
+                cells.push(cell(c).ok_or_else(|| Error::at(input, &line[i..], format!("Unexpected {c:?}")))?);
+            }
+            row_count += 1;
+            if cells.len() != row_count * col_count {
+                return Err(Error::at(input, line, "All rows have to be of the same length"));
+            }
+        }
+        if cells.is_empty() {
+            return Err(Error::new("Empty grid"));
+        }
+
+        Ok(Grid {
+            cells,
+            row_count,
+            col_count,
+        })
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.col_count
+    }
+
+    /// Cell at the given position, `None` if it's outside of the grid
+    pub fn get(&self, (ri, ci): Coord) -> Option<&T> {
+        (ri < self.row_count && ci < self.col_count).then(|| &self.cells[ri * self.col_count + ci])
+    }
+
+    pub fn get_mut(&mut self, (ri, ci): Coord) -> Option<&mut T> {
+        (ri < self.row_count && ci < self.col_count).then(|| &mut self.cells[ri * self.col_count + ci])
+    }
+
+    pub fn row(&self, ri: usize) -> &[T] {
+        &self.cells[ri * self.col_count..(ri + 1) * self.col_count]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.col_count.max(1))
+    }
+
+    pub fn column(&self, ci: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(ci).step_by(self.col_count.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.col_count).map(|ci| self.column(ci))
+    }
+
+    /// All positions of the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Coord> {
+        let col_count = self.col_count;
+        (0..self.row_count).flat_map(move |ri| (0..col_count).map(move |ci| (ri, ci)))
+    }
+
+    /// All cells together with their position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Coordinate a single step away in the given direction, `None` if it's outside of the grid
+    pub fn step(&self, pos: Coord, dir: Direction) -> Option<Coord> {
+        dir.step(pos)
+            .filter(|&(ri, ci)| ri < self.row_count && ci < self.col_count)
+    }
+
+    /// Positions above, right of, below and left of the given one, as far as they are inside of the grid
+    pub fn neighbours(&self, pos: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL.into_iter().filter_map(move |dir| self.step(pos, dir))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Mirrors the grid at its main diagonal, turning rows into columns
+    pub fn transpose(&self) -> Grid<T> {
+        Grid {
+            cells: self.columns().flat_map(|column| column.cloned()).collect(),
+            row_count: self.col_count,
+            col_count: self.row_count,
+        }
+    }
+
+    /// Rotates the grid by 90 degrees, so that the first column becomes the first row in reverse
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        let cells = self
+            .columns()
+            .flat_map(|column| column.cloned().collect::<Vec<_>>().into_iter().rev());
+        Grid {
+            cells: cells.collect(),
+            row_count: self.col_count,
+            col_count: self.row_count,
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Coord) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("Position {:?} is outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, pos: Coord) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("Position {:?} is outside of the grid", pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::Grid;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, input, |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn should_parse_rows() {
+        let grid = digits("123\n456");
+        assert_eq!((2, 3), (grid.row_count(), grid.col_count()));
+        assert_eq!(Some(&6), grid.get((1, 2)));
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(4, grid[(1, 0)]);
+    }
+
+    #[test]
+    fn should_locate_invalid_cells() {
+        let error = Grid::parse("123\n4x6", "123\n4x6", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!("line 2, column 2: Unexpected 'x'", error.to_string());
+        let error = Grid::parse("123\n45", "123\n45", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            "line 2, column 1: All rows have to be of the same length",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn should_iterate_rows_and_columns() {
+        let grid = digits("123\n456");
+        assert_eq!(vec![&[1, 2, 3][..], &[4, 5, 6][..]], grid.rows().collect_vec());
+        assert_eq!(vec![2, 5], grid.column(1).copied().collect_vec());
+        assert_eq!(3, grid.columns().count());
+    }
+
+    #[test]
+    fn should_find_neighbours_inside() {
+        let grid = digits("123\n456");
+        assert_eq!(vec![(0, 1), (1, 0)], grid.neighbours((0, 0)).collect_vec());
+        assert_eq!(vec![(0, 1), (1, 2), (1, 0)], grid.neighbours((1, 1)).collect_vec());
+    }
+
+    #[test]
+    fn should_transpose_and_rotate() {
+        let grid = digits("123\n456");
+        assert_eq!(digits("14\n25\n36"), grid.transpose());
+        assert_eq!(digits("41\n52\n63"), grid.rotate_clockwise());
+        let rotated = (0..4).fold(grid.clone(), |g, _| g.rotate_clockwise());
+        assert_eq!(grid, rotated);
+    }
+}
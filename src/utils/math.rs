@@ -0,0 +1,41 @@
+/// Least common multiple of all values, e.g. the first step at which several independent cycles that all start at
+/// step zero line up again. `1` for no values.
+pub fn lcm_all(values: impl IntoIterator<Item = i64>) -> i64 {
+    values.into_iter().fold(1, num_integer::lcm)
+}
+
+/// Value at `x` of the polynomial of lowest degree through `values` at `0, 1, 2, ...`, via Newton's forward
+/// differences. E.g. `values.len()` for the next value of a series, or `-1` for the value before it.
+pub fn extrapolate(values: &[i64], x: i64) -> i64 {
+    let mut diffs = values.to_vec();
+    let mut result = 0;
+    // Binomial coefficient of x over k, which is an integer for negative x as well
+    let mut binomial = 1;
+    for k in 0..values.len() {
+        result += binomial * diffs[0];
+        binomial = binomial * (x - k as i64) / (k as i64 + 1);
+        diffs = diffs.windows(2).map(|w| w[1] - w[0]).collect();
+        if diffs.iter().all(|&d| d == 0) {
+            break;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn should_compute_lcm_of_all() {
+        assert_eq!(60, super::lcm_all([4, 6, 10]));
+        assert_eq!(1, super::lcm_all([]));
+    }
+
+    #[test]
+    fn should_extrapolate_polynomial() {
+        // n² + 1
+        assert_eq!(101, super::extrapolate(&[1, 2, 5], 10));
+        assert_eq!(2, super::extrapolate(&[1, 2, 5], -1));
+        assert_eq!(7, super::extrapolate(&[7], 3));
+    }
+}
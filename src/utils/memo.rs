@@ -0,0 +1,120 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+/// How often memoized values were reused (hits) or had to be computed (misses)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+}
+
+impl AddAssign for CacheStats {
+    fn add_assign(&mut self, other: CacheStats) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+    }
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rate = 100.0 * self.hits as f64 / self.lookups().max(1) as f64;
+        write!(f, "{} hits, {} misses ({:.1}% hit rate)", self.hits, self.misses, rate)
+    }
+}
+
+thread_local! {
+    static THREAD_STATS: Cell<CacheStats> = const { Cell::new(CacheStats { hits: 0, misses: 0 }) };
+}
+
+/// Statistics of all memos dropped on the current thread since the last call.
+/// A part of a day is solved on a single thread, so taking them before and after attributes them to that part.
+pub fn take_cache_stats() -> CacheStats {
+    THREAD_STATS.take()
+}
+
+/// Memoized values by key, e.g. a tuple of the arguments of a recursive function.
+/// Borrowed keys like `(&str, &[usize])` avoid allocating a key for each call.
+/// Hits and misses are added to the statistics of the current thread when the memo is dropped.
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    stats: CacheStats,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            values: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Value for `key`, computed by `compute` unless it is known already.
+    /// `compute` gets the memo passed, so that recursive calls can use it too.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.values.insert(key, value.clone());
+        value
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        THREAD_STATS.with(|total| {
+            let mut sum = total.get();
+            sum += self.stats;
+            total.set(sum);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{take_cache_stats, CacheStats, Memo};
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_compute(n, |memo| fibonacci(n - 1, memo) + fibonacci(n - 2, memo))
+    }
+
+    #[test]
+    fn should_reuse_values() {
+        let mut memo = Memo::new();
+        assert_eq!(12586269025, fibonacci(50, &mut memo));
+        assert_eq!(CacheStats { hits: 47, misses: 49 }, memo.stats());
+    }
+
+    #[test]
+    fn should_collect_stats_of_dropped_memos() {
+        take_cache_stats();
+        fibonacci(10, &mut Memo::new());
+        fibonacci(10, &mut Memo::new());
+        assert_eq!(CacheStats { hits: 14, misses: 18 }, take_cache_stats());
+        assert_eq!(CacheStats::default(), take_cache_stats());
+    }
+}
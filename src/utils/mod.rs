@@ -1,42 +1,202 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::env;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use std::str::FromStr;
-use std::time::Instant;
+use std::io::{BufRead, BufReader, Read};
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Once, OnceLock};
 
-use itertools::Itertools;
+pub use cycle::{iterate_with_cycle, Cycle, Iterated};
+pub use direction::{Coord, Direction};
+pub use grid::Grid;
+pub use math::{extrapolate, lcm_all};
+pub use memo::{take_cache_stats, CacheStats, Memo};
+pub use normalize::{normalize_input, normalize_lines};
+pub use parse::{
+    blocks, each_line, labelled, labelled_numbers, numbered, parse_at, separated, split_once_at, split_whitespace,
+};
+pub use shortest_path::{a_star, dijkstra, SearchProblem, ShortestPath};
 
-pub fn get_input_file(file_path: &str) -> File {
-    let path_string = format!("./src/{}", &file_path);
-    let path = Path::new(&path_string);
-    return File::open(path).unwrap();
+use crate::error::{Error, Result};
+
+mod cycle;
+mod direction;
+mod grid;
+mod math;
+mod memo;
+mod normalize;
+mod parse;
+mod shortest_path;
+
+/// Environment variable naming the directory the inputs are read from
+pub const INPUT_ROOT_VAR: &str = "AOC_INPUT_DIR";
+
+static INPUT_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// Reads the inputs from below `root` instead, taking precedence over [INPUT_ROOT_VAR].
+/// Only the first call has an effect, it should happen before reading any input.
+pub fn set_input_root(root: impl Into<PathBuf>) {
+    let _ = INPUT_ROOT.set(root.into());
 }
 
-pub fn get_lines(file_path: &str) -> Vec<String> {
-    let file = get_input_file(file_path);
-    let reader = BufReader::new(file);
-    return reader.lines().map(|x| x.unwrap()).collect::<Vec<_>>();
+// Root chosen explicitly via `set_input_root` or the environment, if any
+fn configured_root() -> Option<PathBuf> {
+    INPUT_ROOT.get().cloned().or_else(|| {
+        env::var_os(INPUT_ROOT_VAR)
+            .filter(|root| !root.is_empty())
+            .map(PathBuf::from)
+    })
 }
 
-pub fn part_start(num: i32) -> Instant {
-    println!("**** PART {} ****", num);
-    Instant::now()
+/// Directory the inputs are read from: the configured one if any, otherwise `src` of this crate,
+/// independent of the working directory
+pub fn input_root() -> PathBuf {
+    configured_root().unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("src"))
 }
 
-pub fn part_end(start: Instant) {
-    println!("Finished in {:.2?}", start.elapsed());
+/// File the bench baseline is stored in by default: `target/bench_baseline` of this crate, independent of the working
+/// directory like the inputs
+pub fn default_baseline_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("bench_baseline")
 }
 
-pub fn split_whitespace<T>(target: &str) -> Vec<T>
-where
-    T: FromStr,
-{
-    target
-        .trim()
-        .split_whitespace()
-        .map(|n| {
-            n.parse::<T>()
-                .unwrap_or_else(|_| panic!("Cannot split: {target}"))
-        })
-        .collect_vec()
+/// Path of a file below the input root, e.g. `d01/input`
+pub fn input_path(file_path: &str) -> PathBuf {
+    input_root().join(file_path)
+}
+
+// With the `embed-inputs` feature, the inputs and answers found below `src` at compile time are part of the
+// binary. They are used unless a root was configured explicitly.
+#[cfg(feature = "embed-inputs")]
+fn embedded_input(file_path: &str) -> Option<&'static str> {
+    static EMBEDDED: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+    if configured_root().is_some() {
+        return None;
+    }
+    EMBEDDED
+        .iter()
+        .find(|(path, _)| *path == file_path)
+        .map(|(_, content)| *content)
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn embedded_input(_file_path: &str) -> Option<&'static str> {
+    None
+}
+
+/// Whether there is an input file at the given path below the input root, or embedded into the binary
+pub fn has_input(file_path: &str) -> bool {
+    embedded_input(file_path).is_some() || input_path(file_path).is_file()
+}
+
+pub fn get_input_file(file_path: &str) -> Result<File> {
+    let path = input_path(file_path);
+    File::open(&path).map_err(|err| Error::new(format!("Cannot open {}: {err}", path.display())))
+}
+
+/// Content of the input file, normalized with [normalize_input]
+pub fn get_input(file_path: &str) -> Result<String> {
+    if let Some(input) = embedded_input(file_path) {
+        return Ok(normalize_input(input).into_owned());
+    }
+    let mut input = String::new();
+    get_input_file(file_path)?
+        .read_to_string(&mut input)
+        .map_err(|err| Error::new(format!("Cannot read {file_path}: {err}")))?;
+    Ok(normalize_input(&input).into_owned())
+}
+
+/// Lines of an input, read one at a time
+pub type InputLines<'a> = Box<dyn Iterator<Item = Result<String>> + 'a>;
+
+/// Lines of the input file, read one at a time instead of all at once and normalized with [normalize_lines]
+pub fn read_lines(file_path: &str) -> Result<InputLines<'static>> {
+    if let Some(input) = embedded_input(file_path) {
+        return Ok(Box::new(normalize_lines(
+            input.lines().map(|line| Ok(line.to_string())),
+        )));
+    }
+    Ok(lines_of(BufReader::new(get_input_file(file_path)?)))
+}
+
+/// Lines of `reader`, read one at a time and normalized with [normalize_lines]
+pub fn lines_of<'a>(reader: impl BufRead + 'a) -> InputLines<'a> {
+    let lines = reader
+        .lines()
+        .map(|line| line.map_err(|err| Error::new(format!("Cannot read input: {err}"))));
+    Box::new(normalize_lines(lines))
+}
+
+pub fn get_lines(file_path: &str) -> Result<Vec<String>> {
+    read_lines(file_path)?.collect()
+}
+
+/// Parses each streamed line on its own, passing it to `parse` as both the input and the part to parse.
+/// Errors located within a line are moved to the position of that line in the stream.
+pub fn parse_streamed<'a, T>(
+    lines: &'a mut dyn Iterator<Item = Result<String>>,
+    parse: impl Fn(&str, &str) -> Result<T> + 'a,
+) -> impl Iterator<Item = Result<T>> + 'a {
+    lines.enumerate().map(move |(i, line)| {
+        let line = line?;
+        parse(&line, &line).map_err(|err| err.on_line(i + 1))
+    })
+}
+
+/// Runs the given function, turning a panic into an error holding the panic message
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> std::result::Result<T, String> {
+    QUIET_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANIC.get() {
+                default_hook(info);
+            }
+        }));
+    });
+    let outer = CATCHING_PANIC.replace(true);
+    let result = catch_unwind(AssertUnwindSafe(f)).map_err(panic_message);
+    CATCHING_PANIC.set(outer);
+    result
+}
+
+// Caught panics are reported as errors, so the panic hook must not print them as well. The hook is global to all
+// threads though, so it is replaced once, keeping quiet only on threads that are within `catch_panic`.
+static QUIET_HOOK: Once = Once::new();
+
+thread_local! {
+    static CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn should_resolve_inputs_independent_of_working_directory() {
+        if super::configured_root().is_none() {
+            assert!(super::input_path("d01/input").is_absolute());
+        }
+        assert!(super::get_input("d01/input").is_ok());
+        assert!(super::has_input("d01/input"));
+        assert!(!super::has_input("d01/missing"));
+        assert!(super::default_baseline_path().is_absolute());
+    }
+
+    #[test]
+    fn should_catch_panic_quietly() {
+        let result = super::catch_panic(|| -> u32 { panic!("boom") });
+        assert_eq!(Err(String::from("boom")), result);
+        assert!(!super::CATCHING_PANIC.get());
+    }
 }
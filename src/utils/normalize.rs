@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use crate::error::Result;
+
+const BOM: char = '\u{feff}';
+
+/// Removes what editors and downloads add to an input without changing its meaning: a leading UTF-8 byte order
+/// mark, carriage returns of CRLF line endings and trailing line breaks. Borrows the input if there is none of them.
+pub fn normalize_input(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+    let input = input.trim_end_matches(['\n', '\r']);
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Normalizes streamed lines like [normalize_input] does for a whole input: the byte order mark is removed from
+/// the first line, carriage returns from the end of every line, and empty lines at the end are skipped
+pub fn normalize_lines<'a>(
+    lines: impl Iterator<Item = Result<String>> + 'a,
+) -> impl Iterator<Item = Result<String>> + 'a {
+    // Empty lines are held back until a non-empty line follows, so that trailing ones are never passed on
+    let mut held_back = 0;
+    let mut first = true;
+    lines
+        .map(move |line| {
+            let mut line = line?;
+            if std::mem::take(&mut first) && line.starts_with(BOM) {
+                line.remove(0);
+            }
+            if line.ends_with('\r') {
+                line.pop();
+            }
+            Ok(line)
+        })
+        .flat_map(move |line: Result<String>| {
+            let (empty_lines, line) = match line {
+                Ok(line) if line.is_empty() => {
+                    held_back += 1;
+                    return Vec::new();
+                }
+                line => (std::mem::take(&mut held_back), line),
+            };
+            let mut lines = vec![Ok(String::new()); empty_lines];
+            lines.push(line);
+            lines
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::{normalize_input, normalize_lines};
+
+    fn normalized_lines(input: &str) -> Vec<String> {
+        normalize_lines(input.split('\n').map(|line| Ok(line.to_string())))
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn should_keep_normalized_input() {
+        assert_eq!("a\n\nb", normalize_input("a\n\nb"));
+        assert_eq!(vec!["a", "", "b"], normalized_lines("a\n\nb"));
+    }
+
+    #[test]
+    fn should_remove_trailing_newlines() {
+        assert_eq!("a\nb", normalize_input("a\nb\n"));
+        assert_eq!("a\nb", normalize_input("a\nb\n\n\n"));
+        assert_eq!(vec!["a", "b"], normalized_lines("a\nb\n\n\n"));
+    }
+
+    #[test]
+    fn should_replace_crlf() {
+        assert_eq!("a\nb\n\nc", normalize_input("a\r\nb\r\n\r\nc\r\n"));
+        assert_eq!(vec!["a", "b", "", "c"], normalized_lines("a\r\nb\r\n\r\nc\r\n"));
+    }
+
+    #[test]
+    fn should_remove_bom() {
+        assert_eq!("a\nb", normalize_input("\u{feff}a\nb"));
+        assert_eq!(vec!["a", "b"], normalized_lines("\u{feff}a\nb"));
+        assert_eq!(vec!["a", "b\u{feff}"], normalized_lines("a\nb\u{feff}"));
+    }
+
+    #[test]
+    fn should_normalize_all_at_once() {
+        let input = "\u{feff}#.#\r\n..#\r\n\r\n";
+        assert_eq!("#.#\n..#", normalize_input(input));
+        assert_eq!(normalize_input(input).lines().collect_vec(), normalized_lines(input));
+    }
+}
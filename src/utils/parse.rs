@@ -0,0 +1,126 @@
+use std::any::type_name;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+// Parsers for parts of an input. All of them take the whole `input` and a `target` slice of it, so that errors
+// point to their position within the input.
+
+/// Parses `token`, which has to be a slice of `input`, failing with its position within `input`
+pub fn parse_at<T>(input: &str, token: &str) -> Result<T>
+where
+    T: FromStr,
+{
+    token
+        .parse::<T>()
+        .map_err(|_| Error::at(input, token, format!("Cannot parse {token:?} as {}", type_name::<T>())))
+}
+
+/// Splits `target`, which has to be a slice of `input`, at the first `delimiter`.
+/// Fails with the position of `target` within `input` if there is no delimiter.
+pub fn split_once_at<'a>(input: &str, target: &'a str, delimiter: &str) -> Result<(&'a str, &'a str)> {
+    target
+        .split_once(delimiter)
+        .ok_or_else(|| Error::at(input, target, format!("Missing {delimiter:?}")))
+}
+
+/// Parses all whitespace separated tokens of `target`, which has to be a slice of `input`
+pub fn split_whitespace<T>(input: &str, target: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+{
+    target.split_whitespace().map(|token| parse_at(input, token)).collect()
+}
+
+/// Parses the whitespace separated numbers after the label of a line like `Time: 7 15 30`
+pub fn labelled_numbers<T>(input: &str, line: &str, label: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+{
+    split_whitespace(input, labelled(input, line, label)?)
+}
+
+/// Value of a line like `seeds: 79 14`, trimmed. Fails unless the line starts with `label` followed by a colon.
+pub fn labelled<'a>(input: &str, line: &'a str, label: &str) -> Result<&'a str> {
+    line.strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .map(str::trim)
+        .ok_or_else(|| Error::at(input, line, format!("Expected \"{label}:\"")))
+}
+
+/// Number and trimmed value of a line like `Card 12: 41 48 | 83 86`, with `label` being `Card`
+pub fn numbered<'a, T>(input: &str, line: &'a str, label: &str) -> Result<(T, &'a str)>
+where
+    T: FromStr,
+{
+    let (head, value) = split_once_at(input, line, ":")?;
+    let number = head
+        .strip_prefix(label)
+        .filter(|number| number.starts_with(' '))
+        .ok_or_else(|| Error::at(input, head, format!("Expected \"{label} <number>\"")))?;
+    Ok((parse_at(input, number.trim())?, value.trim()))
+}
+
+/// Parses each trimmed part of `target` between the `separator`s with `item`
+pub fn separated<'a, T>(target: &'a str, separator: &str, item: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+    target.split(separator).map(str::trim).map(item).collect()
+}
+
+/// Parses each line of `target` with `line`
+pub fn each_line<'a, T>(target: &'a str, line: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+    target.lines().map(line).collect()
+}
+
+/// Blocks of lines separated by blank lines, e.g. the patterns of d13, without the line breaks around them
+pub fn blocks(target: &str) -> impl Iterator<Item = &str> {
+    target
+        .split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::{blocks, labelled_numbers, numbered, separated, split_once_at};
+
+    #[test]
+    fn should_parse_labelled_numbers() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!(
+            vec![9, 40, 200],
+            labelled_numbers::<i32>(input, line, "Distance").unwrap()
+        );
+        let error = labelled_numbers::<i32>(input, line, "Time").unwrap_err();
+        assert_eq!("line 2, column 1: Expected \"Time:\"", error.to_string());
+    }
+
+    #[test]
+    fn should_parse_numbered_line() {
+        let input = "Card  1: 41 48 | 83 86\nCard x: 1 | 2";
+        let (first, second) = input.lines().collect_tuple().unwrap();
+        assert_eq!((1, "41 48 | 83 86"), numbered::<u32>(input, first, "Card").unwrap());
+        let error = numbered::<u32>(input, second, "Card").unwrap_err();
+        assert_eq!("line 2, column 6: Cannot parse \"x\" as u32", error.to_string());
+        let error = numbered::<u32>(input, second, "Game").unwrap_err();
+        assert_eq!("line 2, column 1: Expected \"Game <number>\"", error.to_string());
+    }
+
+    #[test]
+    fn should_parse_separated_items() {
+        let input = "3 blue, 4 red; 1 red";
+        let draws = separated(input, ";", |draw| {
+            separated(draw, ",", |d| split_once_at(input, d, " "))
+        })
+        .unwrap();
+        assert_eq!(vec![vec![("3", "blue"), ("4", "red")], vec![("1", "red")]], draws);
+    }
+
+    #[test]
+    fn should_split_blocks() {
+        let input = "a\nb\n\nc\n\n\n\nd";
+        assert_eq!(vec!["a\nb", "c", "d"], blocks(input).collect_vec());
+    }
+}
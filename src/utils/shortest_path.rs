@@ -0,0 +1,156 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+use rustc_hash::FxHashMap;
+
+/// Graph to search for a cheapest path from a start state to any goal state
+pub trait SearchProblem {
+    type State: Clone + Eq + Hash;
+
+    /// States reachable from `state` in a single move, together with the cost of that move
+    fn neighbours(&self, state: &Self::State) -> Vec<(Self::State, u64)>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// Lower bound of the cost from `state` to the closest goal, used by [a_star] to search towards the goal first.
+    /// Overestimating it can result in paths that are not the cheapest.
+    fn heuristic(&self, _state: &Self::State) -> u64 {
+        0
+    }
+}
+
+/// Cheapest path found, from the start to a goal state, both included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPath<S> {
+    pub cost: u64,
+    pub path: Vec<S>,
+}
+
+/// Finds a cheapest path from `start` to a goal state, `None` if no goal is reachable
+pub fn dijkstra<P: SearchProblem>(problem: &P, start: P::State) -> Option<ShortestPath<P::State>> {
+    search(problem, start, |_| 0)
+}
+
+/// Like [dijkstra], but visiting the states ordered by their cost plus the heuristic of the problem
+pub fn a_star<P: SearchProblem>(problem: &P, start: P::State) -> Option<ShortestPath<P::State>> {
+    search(problem, start, |state| problem.heuristic(state))
+}
+
+struct Node<S> {
+    state: S,
+    cost: u64,
+    previous: Option<usize>,
+}
+
+fn search<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+    heuristic: impl Fn(&P::State) -> u64,
+) -> Option<ShortestPath<P::State>> {
+    // States are referred to by their index in `nodes`, the heap is ordered by the lowest estimated total cost
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    // Hashing the states takes most of the time, so the faster (though not DoS resistant) FxHash is used
+    let mut indices = FxHashMap::from_iter([(start.clone(), 0)]);
+    let mut nodes = vec![Node {
+        state: start,
+        cost: 0,
+        previous: None,
+    }];
+
+    while let Some(Reverse((estimate, i))) = heap.pop() {
+        // The state might have been queued again with a lower cost, which was processed already
+        if estimate > nodes[i].cost + heuristic(&nodes[i].state) {
+            continue;
+        }
+        if problem.is_goal(&nodes[i].state) {
+            return Some(reconstruct(nodes, i));
+        }
+
+        for (next, move_cost) in problem.neighbours(&nodes[i].state) {
+            let cost = nodes[i].cost + move_cost;
+            let next_index = match indices.entry(next) {
+                Entry::Occupied(entry) if nodes[*entry.get()].cost <= cost => continue,
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    nodes.push(Node {
+                        state: entry.key().clone(),
+                        cost,
+                        previous: None,
+                    });
+                    *entry.insert(nodes.len() - 1)
+                }
+            };
+            nodes[next_index].cost = cost;
+            nodes[next_index].previous = Some(i);
+            heap.push(Reverse((cost + heuristic(&nodes[next_index].state), next_index)));
+        }
+    }
+
+    None
+}
+
+fn reconstruct<S>(nodes: Vec<Node<S>>, goal: usize) -> ShortestPath<S> {
+    let cost = nodes[goal].cost;
+    let mut indices = vec![goal];
+    while let Some(previous) = nodes[*indices.last().unwrap()].previous {
+        indices.push(previous);
+    }
+
+    let mut nodes = nodes.into_iter().map(Some).collect::<Vec<_>>();
+    let path = indices
+        .into_iter()
+        .rev()
+        .map(|i| nodes[i].take().unwrap().state)
+        .collect();
+    ShortestPath { cost, path }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{a_star, dijkstra, SearchProblem, ShortestPath};
+
+    // Walking along a number line, with a cheap detour via 10
+    struct NumberLine;
+
+    impl SearchProblem for NumberLine {
+        type State = i32;
+
+        fn neighbours(&self, &n: &i32) -> Vec<(i32, u64)> {
+            match n {
+                0 => vec![(1, 5), (10, 1)],
+                10 => vec![(3, 1)],
+                n if n < 5 => vec![(n + 1, 5)],
+                _ => vec![],
+            }
+        }
+
+        fn is_goal(&self, &n: &i32) -> bool {
+            n == 4
+        }
+
+        fn heuristic(&self, &n: &i32) -> u64 {
+            if n <= 4 {
+                4 - n as u64
+            } else {
+                0
+            }
+        }
+    }
+
+    #[test]
+    fn should_find_cheapest_path() {
+        let expected = ShortestPath {
+            cost: 7,
+            path: vec![0, 10, 3, 4],
+        };
+        assert_eq!(Some(expected.clone()), dijkstra(&NumberLine, 0));
+        assert_eq!(Some(expected), a_star(&NumberLine, 0));
+    }
+
+    #[test]
+    fn should_not_find_unreachable_goal() {
+        assert_eq!(None, dijkstra(&NumberLine, 5));
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use aoc_2023::error::Result;
+use aoc_2023::utils::{parse_at, split_once_at};
+use aoc_2023::{utils, SOLUTIONS};
+
+use crate::runner;
+
+/// Runs every registered day on its input and compares the results to the expected answers.
+/// Prints a table of all results and returns whether all of them matched, skipping days without an input.
+pub fn verify() -> bool {
+    let answers = utils::get_input("answers").and_then(|answers| {
+        parse_answers(&answers).map_err(|err| err.in_file(utils::input_path("answers").display().to_string()))
+    });
+    let answers = match answers {
+        Ok(answers) => answers,
+        Err(error) => {
+            eprintln!("{error}");
+            return false;
+        }
+    };
+    let mut success = true;
+
+    println!("{:<5} {:<5} {:<20} {:<20} Status", "Day", "Part", "Expected", "Actual");
+    for &solution in SOLUTIONS {
+        let name = solution.name();
+        let report = runner::run_day(solution, &[1, 2], &None);
+        for part in report.parts {
+            let expected = answers.get(&(name.clone(), part.part));
+            let (actual, status) = match (&part.answer, expected) {
+                (Err(reason), _) if report.skipped => (String::from("-"), format!("SKIPPED: {reason}")),
+                (Err(error), _) => (String::from("-"), format!("ERROR: {error}")),
+                (Ok(actual), None) => (actual.clone(), String::from("NO ANSWER")),
+                (Ok(actual), Some(expected)) if actual == expected => (actual.clone(), String::from("OK")),
+                (Ok(actual), Some(_)) => (actual.clone(), String::from("MISMATCH")),
+            };
+            // Days without an input are skipped, but an answer that cannot be checked is a failure
+            success &= report.skipped || status == "OK";
+            let expected = expected.map_or("-", |e| e.as_str());
+            println!(
+                "{:<5} {:<5} {:<20} {:<20} {}",
+                name, part.part, expected, actual, status
+            );
+        }
+    }
+
+    success
+}
+
+// Parses lines like "d01:2 54249" into a map from (day, part) to answer, ignoring blank lines and comments
+fn parse_answers(input: &str) -> Result<HashMap<(String, u8), String>> {
+    input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (key, answer) = split_once_at(input, line, " ")?;
+            let (day, part) = split_once_at(input, key, ":")?;
+            Ok(((day.to_string(), parse_at(input, part)?), answer.trim().to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn should_parse_answers() {
+        let answers = super::parse_answers("# comment\nd01:1 142\n\nd01:2 281\n").unwrap();
+        assert_eq!(2, answers.len());
+        assert_eq!("142", answers.get(&(String::from("d01"), 1)).unwrap());
+        assert_eq!("281", answers.get(&(String::from("d01"), 2)).unwrap());
+    }
+
+    #[test]
+    fn should_locate_invalid_answer() {
+        let error = super::parse_answers("d01:1 142\nd01:x 281\n").unwrap_err();
+        assert_eq!("line 2, column 5: Cannot parse \"x\" as u8", error.to_string());
+    }
+}
@@ -0,0 +1,60 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// Fresh directory below the system's temporary directory, unique to this process and test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc_2023_{}_{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Runs the binary in the given working directory, ignoring any input directory configured for the tests
+fn run(current_dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc_2023"))
+        .args(args)
+        .current_dir(current_dir)
+        .env_remove("AOC_INPUT_DIR")
+        .output()
+        .unwrap()
+}
+
+fn results(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix("Result: "))
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn should_run_day_outside_of_crate() {
+    let dir = temp_dir("outside_of_crate");
+    let output = run(&dir, &["d01"]);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let answers = include_str!("../src/answers");
+    let expected = ["d01:1 ", "d01:2 "].map(|key| {
+        answers
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .unwrap()
+            .to_string()
+    });
+    assert_eq!(expected.to_vec(), results(&output));
+}
+
+#[test]
+fn should_read_inputs_from_input_dir() {
+    let dir = temp_dir("input_dir");
+    fs::create_dir(dir.join("d01")).unwrap();
+    fs::write(dir.join("d01/input"), "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n").unwrap();
+    let output = run(&dir, &["d01", "--input-dir", dir.to_str().unwrap()]);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(vec!["142", "142"], results(&output));
+}